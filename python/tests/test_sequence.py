@@ -0,0 +1,105 @@
+import pytest
+
+import sequence
+
+
+class FirstMovePlayer:
+    """Plays the first legal move, keeping track of the hooks it is given."""
+
+    def __init__(self):
+        self.seat = None
+        self.opponent_moves = 0
+        self.result = None
+
+    def on_game_start(self, seat, team):
+        self.seat = seat
+
+    def play(self, team, hand, board):
+        return board.legal_moves(team, hand)[0]
+
+    def on_opponent_move(self, seat, team, card, square):
+        self.opponent_moves += 1
+
+    def on_game_end(self, winner, turns):
+        self.result = (winner, turns)
+
+
+class IllegalPlayer:
+    def play(self, team, hand, board):
+        return ("2♠", "a0")
+
+
+def test_bots_play_a_whole_game():
+    game = sequence.Game(["random", "square_evaluation"], seed=1)
+    result = game.run()
+
+    assert result.winner in ("BLUE", "GREEN")
+    assert result.turns == game.turns
+    assert result.seed == 1
+    assert result.sequences[result.winner] >= 2
+    assert game.result.winner == result.winner
+    with pytest.raises(ValueError):
+        game.run_turn()
+
+
+def test_python_players_are_called_back():
+    player = FirstMovePlayer()
+    game = sequence.Game([player, "random"], seed=2)
+    result = game.run()
+
+    assert player.seat == 0
+    assert player.opponent_moves > 0
+    assert player.result == (result.winner, result.turns)
+
+
+def test_errors_in_python_players_are_raised():
+    game = sequence.Game([IllegalPlayer(), "random"], seed=3)
+    hand = game.hand(0)
+    with pytest.raises(ValueError, match="illegal move"):
+        game.run_turn()
+
+    # the illegal move is not replaced with another one, and is asked for again
+    assert game.hand(0) == hand
+    assert all(game.board.chip_at(f"{col}{row}") is None for col in "abcdefghij" for row in range(10))
+    with pytest.raises(ValueError, match="illegal move"):
+        game.run_turn()
+
+
+def test_board():
+    game = sequence.Game(["random", "random"], seed=4)
+    assert game.run_turn() is None
+
+    board = game.board
+    cards = board.cards()
+    assert len(cards) == 10 and cards[0][0] is None
+    assert board.card_at("a0") is None
+    assert sum(board.chip_at(f"{col}{row}") is not None for col in "abcdefghij" for row in range(10)) == 1
+    assert len(game.hand(0)) == 7
+    assert board.legal_moves(game.team(1), game.hand(1))
+    with pytest.raises(ValueError, match="no such seat"):
+        game.team(2)
+    assert len(str(board).splitlines()) == 10
+    assert sequence.Board().sequences() == []
+    with pytest.raises(ValueError):
+        board.card_at("z9")
+
+
+def test_seeds_determine_the_deals():
+    hands = [sequence.Game(["random", "random"], seed=seed).hand(0) for seed in (5, 5, 6)]
+    assert hands[0] == hands[1] != hands[2]
+
+
+def test_simulate():
+    results = sequence.simulate(["square_evaluation", "random"], 3, seed=10)
+
+    assert [result.seed for result in results] == [10, 11, 12]
+    # the seeds determine the random bot's choices as well as the deals
+    replayed = sequence.simulate(["random", "random"], 3, seed=10)
+    assert [(r.winner, r.turns) for r in replayed] == [
+        (r.winner, r.turns) for r in sequence.simulate(["random", "random"], 3, seed=10)
+    ]
+    assert all(result.winner in ("BLUE", "GREEN") for result in results)
+    assert len(sequence.simulate([FirstMovePlayer(), "random"], 2)) == 2
+    with pytest.raises(ValueError, match="unknown bot"):
+        sequence.simulate(["nobody", "random"], 1)
+    assert "random" in sequence.BOTS
@@ -1,10 +1,19 @@
+pub mod belief_tracker;
 pub mod board;
 pub mod card;
+pub mod card_tracker;
 pub mod deck;
+pub mod evaluator;
 pub mod game;
+pub mod moves;
 pub mod grid_traversal;
+pub mod observation;
 pub mod player;
+#[cfg(feature = "analysis")]
+pub mod privileged;
 pub mod rank;
+#[cfg(feature = "serde")]
+pub mod serialization;
 pub mod simple_player;
 pub mod square;
 pub mod standard_board;
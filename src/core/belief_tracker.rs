@@ -0,0 +1,260 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::Rng;
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::card_tracker::CardTracker;
+use crate::core::game::GameConfig;
+use crate::core::team::Team;
+
+// what is known about a single card in another player's hand: None if it could be any unseen
+// card, otherwise the set of cards it must be one of
+type Slot = Option<HashSet<Card>>;
+
+// tracks, from a player's perspective, what each other player may hold based on the events of the
+// game, for use alongside a CardTracker (which knows how many copies of each card are unseen):
+// - when a player skips their turn, every card in their hand must have been unplayable at the time
+// - when a player plays or discards a card, that card leaves their hand (taken from the slot most
+//   likely to hold it) and is replaced by a newly drawn, unknown card
+// the probability of a player holding a card then weights each unseen card by the number of its
+// unseen copies, restricted to the cards each slot may hold; note that slots are treated as
+// independent, which slightly overestimates the chance of holding several copies of a rare card
+pub struct BeliefTracker {
+    seat: usize,
+    // slots for the hand of each player, indexed by seat; empty for this player's own seat
+    hands: Vec<Vec<Slot>>,
+}
+
+impl BeliefTracker {
+    // creates a tracker for the player in the given seat at the start of a game, when nothing is
+    // known about any other hand
+    pub fn new(seat: usize, config: &GameConfig) -> BeliefTracker {
+        let hands = (0..config.num_players)
+            .map(|index| if index == seat { vec![] } else { vec![None; config.hand_size] })
+            .collect();
+        BeliefTracker { seat, hands }
+    }
+
+    // returns the seat of the player which plays after this one
+    pub fn next_seat(&self) -> usize {
+        (self.seat + 1) % self.hands.len()
+    }
+
+    // updates the beliefs after the player in the given seat plays the given card, or discards it
+    // as dead; in both cases they draw a replacement
+    pub fn on_card_discarded(&mut self, seat: usize, card: &Card) {
+        let hand = &mut self.hands[seat];
+
+        // prefer the most constrained slot which could have held the card, since that is where the
+        // card most likely came from
+        let index = hand.iter()
+            .enumerate()
+            .filter_map(|(index, slot)| match slot {
+                Some(cards) if cards.contains(card) => Some((index, cards.len())),
+                Some(_) => None,
+                None => Some((index, usize::MAX)),
+            })
+            .min_by_key(|(_, size)| *size)
+            .map_or(0, |(index, _)| index);
+
+        if !hand.is_empty() {
+            hand.remove(index);
+        }
+        hand.push(None);
+    }
+
+    // updates the beliefs after the player in the given seat (on the given team) skips their turn,
+    // i.e. has no card which can be played on the given board
+    pub fn on_skip(&mut self, seat: usize, team: &Team, board: &Board) {
+        let unplayable: HashSet<Card> = Card::standard_deck()
+            .filter(|card| !board.can_be_played(card, team))
+            .collect();
+
+        for slot in &mut self.hands[seat] {
+            *slot = Some(match slot {
+                Some(cards) => cards.intersection(&unplayable).copied().collect(),
+                None => unplayable.clone(),
+            });
+        }
+    }
+
+    // returns the probability that the player in the given seat holds at least one copy of the
+    // given card
+    pub fn hold_probability(&self, seat: usize, card: &Card, tracker: &CardTracker) -> f64 {
+        let none_held: f64 = self.hands[seat].iter()
+            .map(|slot| 1.0 - slot_probability(slot, card, tracker))
+            .product();
+        1.0 - none_held
+    }
+
+    // returns the expected number of copies of each unseen card in the hand of the player in the
+    // given seat; these sum to the size of their hand
+    pub fn hand_distribution(&self, seat: usize, tracker: &CardTracker) -> HashMap<Card, f64> {
+        let mut distribution = HashMap::new();
+        for slot in &self.hands[seat] {
+            for (card, _) in tracker.unseen() {
+                let probability = slot_probability(slot, card, tracker);
+                if probability > 0.0 {
+                    *distribution.entry(*card).or_insert(0.0) += probability;
+                }
+            }
+        }
+        distribution
+    }
+
+    // samples a hand for every other player consistent with the beliefs (as far as possible) and
+    // the unseen cards, without replacement; the hand for this player's own seat is empty
+    pub fn sample_hands<R: Rng>(&self, tracker: &CardTracker, rng: &mut R) -> Vec<Vec<Card>> {
+        let mut remaining: HashMap<Card, u8> = tracker.unseen()
+            .map(|(card, count)| (*card, *count))
+            .collect();
+
+        // fill the most constrained slots first, so that unconstrained ones do not use up the only
+        // cards the constrained ones may hold
+        let mut slots: Vec<(usize, &Slot)> = self.hands.iter()
+            .enumerate()
+            .flat_map(|(seat, hand)| hand.iter().map(move |slot| (seat, slot)))
+            .collect();
+        slots.sort_by_key(|(_, slot)| slot.as_ref().map_or(usize::MAX, |cards| cards.len()));
+
+        let mut hands = vec![vec![]; self.hands.len()];
+        for (seat, slot) in slots {
+            let allowed = |card: &Card| slot.as_ref().map_or(true, |cards| cards.contains(card));
+            let card = sample_card(&remaining, allowed, rng)
+                // the beliefs are inconsistent with the unseen cards; fall back to any card
+                .or_else(|| sample_card(&remaining, |_| true, rng));
+
+            if let Some(card) = card {
+                *remaining.get_mut(&card).unwrap() -= 1;
+                hands[seat].push(card);
+            }
+        }
+
+        hands
+    }
+}
+
+// returns the probability that the card in the given slot is the given card
+fn slot_probability(slot: &Slot, card: &Card, tracker: &CardTracker) -> f64 {
+    let unknown_probability = || {
+        if tracker.unseen_total() == 0 {
+            0.0
+        } else {
+            tracker.unseen_count(card) as f64 / tracker.unseen_total() as f64
+        }
+    };
+    let Some(cards) = slot else { return unknown_probability() };
+
+    let total: usize = cards.iter().map(|card| tracker.unseen_count(card) as usize).sum();
+    if total == 0 {
+        // the beliefs are inconsistent with the unseen cards; treat the slot as unknown
+        unknown_probability()
+    } else if cards.contains(card) {
+        tracker.unseen_count(card) as f64 / total as f64
+    } else {
+        0.0
+    }
+}
+
+// samples one of the remaining cards allowed by the given predicate, weighted by its number of
+// remaining copies
+fn sample_card<F, R>(remaining: &HashMap<Card, u8>, allowed: F, rng: &mut R) -> Option<Card>
+    where F: Fn(&Card) -> bool, R: Rng {
+    let total: usize = remaining.iter()
+        .filter(|(card, _)| allowed(card))
+        .map(|(_, count)| *count as usize)
+        .sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut choice = rng.gen_range(0..total);
+    for (card, count) in remaining.iter().filter(|(card, _)| allowed(card)) {
+        if choice < *count as usize {
+            return Some(*card);
+        }
+        choice -= *count as usize;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use crate::core::deck::Deck;
+    use crate::core::game::Game;
+    use crate::core::rank::Rank;
+    use crate::core::suit::Suit;
+
+    use super::*;
+
+    fn config() -> GameConfig {
+        GameConfig {
+            num_players: 2,
+            num_teams: 2,
+            hand_size: Game::hand_size(2),
+            winning_sequences: Game::winning_sequences(2),
+            max_dead_card_exchanges: 1,
+        }
+    }
+
+    #[test]
+    fn skipped_turn_rules_out_playable_cards() {
+        let mut board = Board::standard_board();
+        let deck = Deck::default();
+        let tracker = CardTracker::new(&[], &deck);
+
+        // both squares of the three of clubs are taken, so it is dead
+        let three_of_clubs = Card { rank: Rank::Three, suit: Suit::Clubs };
+        for square in board.squares_for_card(&three_of_clubs).unwrap() {
+            board.add_chip(&square, Team::One);
+        }
+
+        let mut beliefs = BeliefTracker::new(0, &config());
+        let two_eyed_jack = Card { rank: Rank::Jack, suit: Suit::Clubs };
+        assert!(beliefs.hold_probability(1, &two_eyed_jack, &tracker) > 0.0);
+
+        beliefs.on_skip(1, &Team::Two, &board);
+        assert_eq!(beliefs.hold_probability(1, &two_eyed_jack, &tracker), 0.0);
+        assert_eq!(beliefs.hold_probability(1, &three_of_clubs, &tracker), 1.0);
+
+        // after playing a card, one slot is unknown again
+        beliefs.on_card_discarded(1, &three_of_clubs);
+        assert!(beliefs.hold_probability(1, &two_eyed_jack, &tracker) > 0.0);
+
+        let distribution = beliefs.hand_distribution(1, &tracker);
+        let total: f64 = distribution.values().sum();
+        assert!((total - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sampled_hands_respect_beliefs() {
+        let mut board = Board::standard_board();
+        let deck = Deck::default();
+        let tracker = CardTracker::new(&[], &deck);
+
+        // kill four cards, i.e. eight copies, enough for a full hand
+        let dead_cards: Vec<Card> = Card::standard_deck()
+            .filter(|card| card.rank != Rank::Jack)
+            .take(4)
+            .collect();
+        for card in &dead_cards {
+            for square in board.squares_for_card(card).unwrap() {
+                board.add_chip(&square, Team::One);
+            }
+        }
+
+        let mut beliefs = BeliefTracker::new(0, &config());
+        beliefs.on_skip(1, &Team::Two, &board);
+
+        for _ in 0..10 {
+            let hands = beliefs.sample_hands(&tracker, &mut thread_rng());
+            assert!(hands[0].is_empty());
+            assert_eq!(hands[1].len(), 7);
+            assert!(hands[1].iter().all(|card| dead_cards.contains(card)));
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::{HashMap, HashSet};
 use crate::core::card::Card;
 use crate::core::square::Square;
 use crate::core::team::Team;
+use crate::render::text::{BoardText, TextOptions};
 use crate::util::wrapper::Wrapper;
 
 pub const BOARD_SIZE: u8 = 10;
@@ -10,6 +11,7 @@ pub const SEQUENCE_LENGTH: u8 = 5;
 pub const NUM_PLAYABLE_SQUARES: u8 = BOARD_SIZE * BOARD_SIZE - 4;
 
 // TODO relax requirements on board to allow different sizes and arrangements of wildcard squares
+#[derive(Clone)]
 pub struct Board {
     cards: [[Option<Card>; BOARD_SIZE as usize]; BOARD_SIZE as usize],
     chips: [[Option<Team>; BOARD_SIZE as usize]; BOARD_SIZE as usize],
@@ -101,6 +103,63 @@ impl Board {
         board
     }
 
+    // creates a board with the given cards, chips and completed sequences (e.g. as received from
+    // elsewhere), rebuilding the derived indexes; fails if the cards are not laid out like a board's
+    // (no cards on the corners, and two of each card other than jacks on the other squares) or the
+    // chips and sequences are inconsistent
+    pub fn restore(
+        cards: [[Option<Card>; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+        chips: [[Option<Team>; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+        sequences: Vec<(Team, HashSet<Square>)>,
+    ) -> Result<Board, String> {
+        let mut card_counts: HashMap<Card, usize> = HashMap::new();
+        for square in Square::all_squares() {
+            match cards[square.row as usize][square.col as usize] {
+                Some(card) if square.is_corner() => return Err(format!("card {card} on corner {square}")),
+                Some(card) if card.is_one_eyed_jack() || card.is_two_eyed_jack() => {
+                    return Err(format!("jack {card} on square {square}"));
+                }
+                Some(card) => *card_counts.entry(card).or_default() += 1,
+                None if !square.is_corner() => return Err(format!("no card on square {square}")),
+                None => {}
+            }
+        }
+        if let Some((card, count)) = card_counts.iter().find(|(_, count)| **count != 2) {
+            return Err(format!("card {card} is on {count} squares instead of 2"));
+        }
+
+        let mut board = Board::new(cards);
+
+        for square in Square::all_squares() {
+            if let Some(team) = chips[square.row as usize][square.col as usize] {
+                if !square.is_playable() {
+                    return Err(format!("chip on non-playable square {square}"));
+                }
+
+                board.chips[square.row as usize][square.col as usize] = Some(team);
+                board.num_chips += 1;
+                board.team_to_squares.entry(team).or_default().insert(square);
+            }
+        }
+
+        for (team, squares) in &sequences {
+            if let Some(square) = squares.iter().find(|square| board.chip_at(square) != Some(*team)) {
+                return Err(format!("sequence for {team} includes square {square} without its chip"));
+            }
+            board.squares_in_sequence.extend(squares);
+        }
+        board.sequences = sequences;
+
+        board.assert_invariants();
+
+        Ok(board)
+    }
+
+    // returns the cards on each square, indexed by row and then column; None for corners
+    pub fn cards(&self) -> &[[Option<Card>; BOARD_SIZE as usize]; BOARD_SIZE as usize] {
+        &self.cards
+    }
+
     // returns the card at the given square; None for corners
     pub fn card_at(&self, square: &Square) -> Option<Card> {
         self.cards[square.row as usize][square.col as usize]
@@ -155,10 +214,29 @@ impl Board {
         square.is_corner() || self.chip_at(square).wraps(team)
     }
 
+    // returns the number of chips the given team has on the board
+    pub fn num_chips_for(&self, team: &Team) -> usize {
+        self.team_to_squares.get(team).map_or(0, |squares| squares.len())
+    }
+
     pub fn squares_owned_by(&self, team: &Team) -> HashSet<Square> {
         self.team_to_squares[team].clone()
     }
 
+    // returns all fully formed sequences, paired with the team owning them
+    pub fn sequences(&self) -> &[(Team, HashSet<Square>)] {
+        &self.sequences
+    }
+
+    // returns the fully formed sequences owned by the given team
+    pub fn sequences_for(&self, team: &Team) -> impl Iterator<Item=&HashSet<Square>> {
+        let team = *team;
+        self.sequences.iter()
+            .filter_map(move |(sequence_team, squares)| {
+                if *sequence_team == team { Some(squares) } else { None }
+            })
+    }
+
     // returns true if the given square is in a sequence
     pub fn in_sequence(&self, square: &Square) -> bool {
         self.squares_in_sequence.contains(square)
@@ -195,38 +273,14 @@ impl Board {
         result
     }
 
+    // prints the board with colors unless NO_COLOR is set (see render::text for other options)
     pub fn print(&self) {
         self.print_with_highlighted_cards(&HashSet::new())
     }
 
     pub fn print_with_highlighted_cards(&self, cards: &HashSet<Card>) {
-        for (i, row) in self.cards.iter().enumerate() {
-            for (j, card) in row.iter().enumerate() {
-                let square = Square { row: i as u8, col: j as u8 };
-                let card_str = match card {
-                    None => String::from("--"),
-                    Some(c) => format!("{}", c),
-                };
-                let base = format!("{square} {card_str}");
-                let colored = match self.chips[i][j] {
-                    None => {
-                        if let Some(card) = self.cards[i][j] {
-                            if cards.contains(&card) {
-                                format!("\x1b[33m{base}\x1b[39m")
-                            } else {
-                                base
-                            }
-                        } else {
-                            base
-                        }
-                    }
-                    Some(team) => team.with_team_color(&base, self.in_sequence(&square))
-                };
-
-                print!("| {colored} ")
-            }
-            println!("|")
-        }
+        let options = TextOptions { highlighted_cards: cards.clone(), ..TextOptions::from_env() };
+        print!("{}", BoardText::new(self, &options));
     }
 
     // returns the number of sequences owned by team if new one(s) were created
@@ -1,4 +1,5 @@
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 use itertools::iproduct;
 use strum::IntoEnumIterator;
@@ -18,6 +19,24 @@ impl Display for Card {
     }
 }
 
+// parses the notation produced by Display, e.g. "T♠", or the same with an ASCII suit letter
+// (s, h, d or c), e.g. "Ts"
+impl FromStr for Card {
+    type Err = String;
+
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        let mut chars = notation.chars();
+        let (Some(rank_char), Some(suit_char), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(format!("invalid card: {notation}"));
+        };
+
+        let rank = rank_char.to_string().parse().map_err(|_| format!("invalid rank in card: {notation}"))?;
+        let suit = suit_char.to_string().parse().map_err(|_| format!("invalid suit in card: {notation}"))?;
+
+        Ok(Card { rank, suit })
+    }
+}
+
 impl Card {
     // one-eyed jacks remove
     pub fn is_one_eyed_jack(&self) -> bool {
@@ -29,9 +48,38 @@ impl Card {
         self.rank == Rank::Jack && (self.suit == Suit::Diamonds || self.suit == Suit::Clubs)
     }
 
+    // returns the position of this card in standard_deck(), i.e. a unique index in 0..52
+    pub fn index(&self) -> usize {
+        self.suit as usize * Rank::iter().len() + self.rank as usize
+    }
+
     // returns a new vector of cards where each suit/rank combination is represented exactly once
     pub fn standard_deck() -> impl Iterator<Item = Card> {
         iproduct!(Suit::iter(), Rank::iter())
             .map(|(suit, rank)| Card { suit, rank })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_matches_position_in_standard_deck() {
+        for (index, card) in Card::standard_deck().enumerate() {
+            assert_eq!(index, card.index());
+        }
+    }
+
+    #[test]
+    fn parse_notation() {
+        for card in Card::standard_deck() {
+            assert_eq!(card.to_string().parse(), Ok(card));
+        }
+        assert_eq!("Ts".parse(), Ok(Card { rank: Rank::Ten, suit: Suit::Spades }));
+        assert_eq!("Jd".parse(), Ok(Card { rank: Rank::Jack, suit: Suit::Diamonds }));
+        assert!("1s".parse::<Card>().is_err());
+        assert!("Tx".parse::<Card>().is_err());
+        assert!("T".parse::<Card>().is_err());
+    }
+}
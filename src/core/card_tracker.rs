@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::{Deck, DiscardReason};
+use crate::core::rank::Rank;
+use crate::core::square::Square;
+
+// number of copies of each card in the full (double) deck
+const COPIES_PER_CARD: u8 = 2;
+
+// tracks which cards a player has seen over the course of the game, from the publicly visible
+// discards and their own hand, and therefore which cards remain unseen (i.e. are either in the
+// draw pile or in other players' hands)
+// - since all discards are shuffled back into the draw pile when it is exhausted, only discards
+//   since the last reshuffle count as seen; the full history is still available
+pub struct CardTracker {
+    played: Vec<Card>,
+    dead: Vec<Card>,
+    hand: Vec<Card>,
+
+    // number of unseen copies of each card; cards with no unseen copies are not present
+    unseen: HashMap<Card, u8>,
+    unseen_total: usize,
+}
+
+impl CardTracker {
+    // creates a tracker from the perspective of a player holding the given hand
+    pub fn new(hand: &[Card], deck: &Deck) -> CardTracker {
+        let mut unseen: HashMap<Card, u8> = Card::standard_deck()
+            .map(|card| (card, COPIES_PER_CARD))
+            .collect();
+
+        for card in deck.discard_pile().iter().chain(hand) {
+            if let Some(count) = unseen.get_mut(card) {
+                *count = count.saturating_sub(1);
+            }
+        }
+        unseen.retain(|_, count| *count > 0);
+
+        let unseen_total = unseen.values().map(|count| *count as usize).sum();
+
+        let mut played = vec![];
+        let mut dead = vec![];
+        for (card, reason) in deck.history() {
+            match reason {
+                DiscardReason::Played => played.push(*card),
+                DiscardReason::Dead => dead.push(*card),
+            }
+        }
+
+        CardTracker { played, dead, hand: hand.to_vec(), unseen, unseen_total }
+    }
+
+    // returns every card played so far in the game, in order
+    pub fn played(&self) -> &[Card] {
+        &self.played
+    }
+
+    // returns every card discarded as dead so far in the game, in order
+    pub fn dead(&self) -> &[Card] {
+        &self.dead
+    }
+
+    pub fn hand(&self) -> &[Card] {
+        &self.hand
+    }
+
+    // returns the number of unseen copies of the given card
+    pub fn unseen_count(&self, card: &Card) -> u8 {
+        self.unseen.get(card).copied().unwrap_or(0)
+    }
+
+    // returns the total number of unseen cards
+    pub fn unseen_total(&self) -> usize {
+        self.unseen_total
+    }
+
+    // returns each unseen card with its number of unseen copies
+    pub fn unseen(&self) -> impl Iterator<Item=(&Card, &u8)> {
+        self.unseen.iter()
+    }
+
+    pub fn unseen_jacks(&self) -> usize {
+        self.unseen_matching(|card| card.rank == Rank::Jack)
+    }
+
+    pub fn unseen_one_eyed_jacks(&self) -> usize {
+        self.unseen_matching(|card| card.is_one_eyed_jack())
+    }
+
+    pub fn unseen_two_eyed_jacks(&self) -> usize {
+        self.unseen_matching(|card| card.is_two_eyed_jack())
+    }
+
+    // returns the probability of drawing at least one copy of the given card within the given
+    // number of draws, assuming that every unseen card is equally likely to be drawn
+    pub fn draw_probability(&self, card: &Card, draws: usize) -> f64 {
+        self.draw_any_probability(self.unseen_count(card) as usize, draws)
+    }
+
+    // returns the probability of drawing at least one card which could place a chip on the given
+    // square (i.e. the card on the square or a two-eyed jack) within the given number of draws;
+    // 0 for corners
+    pub fn square_draw_probability(&self, square: &Square, board: &Board, draws: usize) -> f64 {
+        match board.card_at(square) {
+            Some(card) => {
+                let copies = self.unseen_count(&card) as usize + self.unseen_two_eyed_jacks();
+                self.draw_any_probability(copies, draws)
+            }
+            None => 0.0,
+        }
+    }
+
+    fn unseen_matching<F>(&self, predicate: F) -> usize where F: Fn(&Card) -> bool {
+        self.unseen.iter()
+            .filter(|(card, _)| predicate(card))
+            .map(|(_, count)| *count as usize)
+            .sum()
+    }
+
+    // returns the probability of drawing at least one of the given number of matching cards within
+    // the given number of draws from the unseen cards, i.e. one minus the (hypergeometric)
+    // probability of drawing none of them
+    fn draw_any_probability(&self, matching: usize, draws: usize) -> f64 {
+        let total = self.unseen_total;
+        if matching == 0 || draws == 0 {
+            return 0.0;
+        }
+        if draws + matching > total {
+            return 1.0;
+        }
+
+        let none_drawn: f64 = (0..draws)
+            .map(|i| (total - matching - i) as f64 / (total - i) as f64)
+            .product();
+        1.0 - none_drawn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::suit::Suit;
+
+    use super::*;
+
+    #[test]
+    fn new_game_tracker_only_sees_hand() {
+        let deck = Deck::new();
+        let hand = vec![
+            Card { rank: Rank::Two, suit: Suit::Clubs },
+            Card { rank: Rank::Jack, suit: Suit::Spades },
+        ];
+
+        let tracker = CardTracker::new(&hand, &deck);
+        assert_eq!(tracker.unseen_total(), 102);
+        assert_eq!(tracker.unseen_count(&hand[0]), 1);
+        assert_eq!(tracker.unseen_count(&Card { rank: Rank::Two, suit: Suit::Spades }), 2);
+        assert_eq!(tracker.unseen_jacks(), 7);
+        assert_eq!(tracker.unseen_one_eyed_jacks(), 3);
+        assert_eq!(tracker.unseen_two_eyed_jacks(), 4);
+    }
+
+    #[test]
+    fn discards_are_seen_until_reshuffled() {
+        let mut deck = Deck::new();
+        let card = Card { rank: Rank::Ace, suit: Suit::Hearts };
+        deck.discard(card);
+        deck.discard_with_reason(card, DiscardReason::Dead);
+
+        let tracker = CardTracker::new(&[], &deck);
+        assert_eq!(tracker.unseen_count(&card), 0);
+        assert_eq!(tracker.played(), &[card]);
+        assert_eq!(tracker.dead(), &[card]);
+        assert_eq!(tracker.draw_probability(&card, 10), 0.0);
+
+        // drawing the remaining draw pile triggers a reshuffle of the discards
+        while deck.draw_pile_size() > 0 {
+            deck.draw();
+        }
+        deck.draw();
+
+        let tracker = CardTracker::new(&[], &deck);
+        assert_eq!(tracker.unseen_count(&card), 2);
+        assert_eq!(tracker.played(), &[card]);
+    }
+
+    #[test]
+    fn draw_probabilities() {
+        let deck = Deck::new();
+        let tracker = CardTracker::new(&[], &deck);
+        let card = Card { rank: Rank::Ace, suit: Suit::Hearts };
+
+        assert_eq!(tracker.draw_probability(&card, 0), 0.0);
+        assert!((tracker.draw_probability(&card, 1) - 2.0 / 104.0).abs() < 1e-9);
+        assert!((tracker.draw_probability(&card, 2) - (1.0 - (102.0 / 104.0) * (101.0 / 103.0))).abs() < 1e-9);
+        assert_eq!(tracker.draw_probability(&card, 103), 1.0);
+
+        // the ace of hearts or any of the four two-eyed jacks
+        let board = Board::standard_board();
+        let square = *board.squares_for_card(&card).unwrap().iter().next().unwrap();
+        assert!((tracker.square_draw_probability(&square, &board, 1) - 6.0 / 104.0).abs() < 1e-9);
+        assert_eq!(tracker.square_draw_probability(&Square { row: 0, col: 0 }, &board, 1), 0.0);
+    }
+}
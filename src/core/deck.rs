@@ -1,5 +1,6 @@
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::SeedableRng;
 
 use crate::core::card::Card;
 
@@ -11,6 +12,24 @@ pub struct Deck {
     // appears to be equivalent (except perhaps for seeing which players played which cards, given
     // that dead cards could interrupt the order)
     discard_pile: Vec<Card>,
+
+    // every card discarded since the start of the game (including those which have since been
+    // reshuffled into the draw pile), in order, with the reason it was discarded
+    history: Vec<(Card, DiscardReason)>,
+    // number of times the discard pile has been shuffled into the draw pile
+    reshuffles: usize,
+
+    // the source of every shuffle, so that a deck created with a seed always deals the same cards
+    rng: StdRng,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(rename_all = "lowercase"))]
+pub enum DiscardReason {
+    // the card was played to place or remove a chip
+    Played,
+    // the card was exchanged as a dead card
+    Dead,
 }
 
 impl Deck {
@@ -18,8 +37,9 @@ impl Deck {
         // reshuffle the discard into the draw pile if empty
         if self.draw_pile.is_empty() {
             self.draw_pile.append(&mut self.discard_pile);
-            self.draw_pile.shuffle(&mut thread_rng());
+            self.draw_pile.shuffle(&mut self.rng);
             self.discard_pile.clear();
+            self.reshuffles += 1;
         }
 
         self.draw_pile.pop().unwrap_or_else(|| panic!("empty deck"))
@@ -29,20 +49,85 @@ impl Deck {
         self.draw_pile.len()
     }
 
+    // returns the draw pile in reverse order of drawing; only available for analysis since players
+    // should not be able to see it
+    #[cfg(feature = "analysis")]
+    pub(crate) fn draw_pile(&self) -> &[Card] {
+        &self.draw_pile
+    }
+
     pub fn discard_pile(&self) -> &[Card] {
         &self.discard_pile
     }
 
+    // returns every card discarded so far in the game, including those which have since been
+    // reshuffled into the draw pile
+    pub fn history(&self) -> &[(Card, DiscardReason)] {
+        &self.history
+    }
+
+    pub fn reshuffles(&self) -> usize {
+        self.reshuffles
+    }
+
+    // discards a card which was played
     pub fn discard(&mut self, card: Card) {
+        self.discard_with_reason(card, DiscardReason::Played);
+    }
+
+    pub fn discard_with_reason(&mut self, card: Card, reason: DiscardReason) {
         self.discard_pile.push(card);
+        self.history.push((card, reason));
     }
 
     pub fn new() -> Deck {
+        Deck::with_rng(StdRng::from_entropy())
+    }
+
+    // creates a deck whose shuffles are all determined by the given seed
+    pub fn with_seed(seed: u64) -> Deck {
+        Deck::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(mut rng: StdRng) -> Deck {
         // sequence deck contains two copies of a standard deck, shuffled
         let mut draw_pile: Vec<Card> = Card::standard_deck().chain(Card::standard_deck()).collect();
-        draw_pile.shuffle(&mut thread_rng());
+        draw_pile.shuffle(&mut rng);
+
+        Deck { draw_pile, discard_pile: vec![], history: vec![], reshuffles: 0, rng }
+    }
+
+    // creates a deck from only its public state, e.g. for a player outside of the game: the size of
+    // the draw pile, the number of reshuffles so far, and the history of discards (the last
+    // discard_pile_size of which are still in the discard pile)
+    // - the contents of the draw pile are unknown and so arbitrary
+    pub fn from_public_state(
+        draw_pile_size: usize,
+        reshuffles: usize,
+        history: Vec<(Card, DiscardReason)>,
+        discard_pile_size: usize,
+    ) -> Result<Deck, String> {
+        if discard_pile_size > history.len() {
+            return Err(format!("discard pile size {} exceeds history of {} cards",
+                               discard_pile_size, history.len()));
+        }
+
+        let draw_pile = (0..draw_pile_size)
+            .flat_map(|_| Card::standard_deck())
+            .take(draw_pile_size)
+            .collect();
+        let discard_pile = history[history.len() - discard_pile_size..].iter()
+            .map(|(card, _)| *card)
+            .collect();
+
+        // the draw pile is arbitrary anyway, so its shuffles need not be unpredictable
+        Ok(Deck { draw_pile, discard_pile, history, reshuffles, rng: StdRng::seed_from_u64(0) })
+    }
 
-        Deck { draw_pile, discard_pile: vec![] }
+    // returns a copy of this deck with only its public state, i.e. with an arbitrary draw pile
+    pub fn public_copy(&self) -> Deck {
+        Deck::from_public_state(self.draw_pile_size(), self.reshuffles, self.history.clone(), self.discard_pile.len())
+            .unwrap()
     }
 }
 
@@ -98,5 +183,19 @@ mod tests {
 
         deck.assert_draw_pile_contains(&deck_minus_last_card);
         assert!(deck.discard_pile().is_empty());
+        assert_eq!(1, deck.reshuffles());
+        assert_eq!(discarded_cards.len(), deck.history().len());
+    }
+
+    #[test]
+    fn seeded_decks_deal_the_same_cards() {
+        let deal = |mut deck: Deck| (0..200).map(|_| {
+            let card = deck.draw();
+            deck.discard(card);
+            card
+        }).collect::<Vec<Card>>();
+
+        assert_eq!(deal(Deck::with_seed(7)), deal(Deck::with_seed(7)));
+        assert_ne!(deal(Deck::with_seed(7)), deal(Deck::with_seed(8)));
     }
 }
@@ -0,0 +1,12 @@
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::team::Team;
+
+// static evaluation of a whole position, for use by search players, tuning, etc
+pub trait Evaluator {
+    // scores the board for the given team, where higher is better; the hand of the team's player
+    // may be given if it is known, otherwise only the board is considered
+    // - evaluations should be symmetric, i.e. the score for one team should be (roughly) the
+    //   negation of the score for its strongest opponent
+    fn evaluate(&self, board: &Board, team: &Team, hand: Option<&[Card]>) -> f32;
+}
@@ -1,10 +1,18 @@
+#[cfg(feature = "analysis")]
+use std::collections::HashMap;
+use std::collections::HashSet;
+
 use crate::core::board::Board;
 use crate::core::card::Card;
-use crate::core::deck::Deck;
+use crate::core::deck::{Deck, DiscardReason};
+use crate::core::moves::Move;
 use crate::core::player::Player;
+#[cfg(feature = "analysis")]
+use crate::core::privileged::{PrivilegedPlayer, PrivilegedView};
 use crate::core::square::Square;
 use crate::core::team::Team;
 use crate::log::LogLevel;
+use crate::render::text::{BoardText, TextOptions};
 use crate::util::generate_vector;
 
 pub struct Game {
@@ -16,8 +24,30 @@ pub struct Game {
     player_hands: Vec<Vec<Card>>,
     board: Board,
     deck: Deck,
+    // maximum number of dead cards a player may exchange at the start of their turn, before
+    // choosing the card to play; the official rules allow one
+    max_dead_card_exchanges: usize,
+    // set once the dead card exchanges of the current turn have been made (see
+    // exchange_dead_cards()), to whether the player who is up then has any playable cards
+    exchanged: Option<bool>,
+    // seats whose moves are chosen by a player with a full-information view of the game instead
+    // of their regular player
+    #[cfg(feature = "analysis")]
+    privileged_players: HashMap<usize, Box<dyn PrivilegedPlayer>>,
+}
+
+// the rules of a game, which are fixed once it has started
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GameConfig {
+    pub num_players: usize,
+    pub num_teams: usize,
+    pub hand_size: usize,
+    pub winning_sequences: usize,
+    pub max_dead_card_exchanges: usize,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct GameResult {
     pub winner: Team,
     pub turns: usize,
@@ -25,15 +55,22 @@ pub struct GameResult {
 
 impl Game {
     // creates a new game
-    // TODO pass through same RNG source to deck
     pub fn new(players: Vec<Box<dyn Player>>, num_teams: usize) -> Game {
+        Game::with_deck(players, num_teams, Deck::new())
+    }
+
+    // creates a new game whose deals are all determined by the given seed (though the players may
+    // still make random choices of their own)
+    pub fn with_seed(players: Vec<Box<dyn Player>>, num_teams: usize, seed: u64) -> Game {
+        Game::with_deck(players, num_teams, Deck::with_seed(seed))
+    }
+
+    fn with_deck(players: Vec<Box<dyn Player>>, num_teams: usize, mut deck: Deck) -> Game {
         debug_assert!(
-            num_teams % players.len() == 0,
+            players.len() % num_teams == 0,
             "invalid number of teams: {} for {} players", num_teams, players.len(),
         );
 
-        let mut deck = Deck::new();
-
         let hand_size = Game::hand_size(players.len());
         let player_hands = generate_vector(players.len(), |_| {
             generate_vector(hand_size, |_| deck.draw())
@@ -47,9 +84,28 @@ impl Game {
             board: Board::standard_board(),
             deck,
             turn_count: 0,
+            max_dead_card_exchanges: 1,
+            exchanged: None,
+            #[cfg(feature = "analysis")]
+            privileged_players: HashMap::new(),
         }
     }
 
+    // replaces the player at the given index with one which is given a full-information view of
+    // the game (including the draw pile and other players' hands) on each of its turns; the regular
+    // player at that index is no longer consulted
+    #[cfg(feature = "analysis")]
+    pub fn set_privileged_player(&mut self, player_index: usize, player: Box<dyn PrivilegedPlayer>) {
+        debug_assert!(player_index < self.players.len(), "privileged player index out of bounds");
+        self.privileged_players.insert(player_index, player);
+    }
+
+    // sets the maximum number of dead cards a player may exchange at the start of each turn; 0
+    // disables exchanging dead cards
+    pub fn set_max_dead_card_exchanges(&mut self, max_dead_card_exchanges: usize) {
+        self.max_dead_card_exchanges = max_dead_card_exchanges;
+    }
+
     pub fn run(&mut self) -> GameResult {
         loop {
             if let Some(winner) = self.run_turn() {
@@ -58,31 +114,72 @@ impl Game {
         }
     }
 
-    pub fn run_turn(&mut self) -> Option<GameResult> {
+    // starts the next turn by making its dead card exchanges, if that has not been done already;
+    // the rest of the turn is run by run_turn(), which calls this itself, so this is only needed to
+    // let a player see their replacement cards before choosing their move (e.g. when their moves
+    // are submitted from elsewhere)
+    pub fn exchange_dead_cards(&mut self) {
+        if self.exchanged.is_some() {
+            return;
+        }
+
+        if self.turn_count == 0 {
+            let config = self.config();
+            for (seat, player) in self.players.iter_mut().enumerate() {
+                player.on_game_start(seat, &Game::player_team(config.num_teams, seat), &config);
+            }
+        }
+
         self.turn_count += 1;
 
         // optionally replace a dead card
-        let has_playable_card = self.replace_dead_card();
+        self.exchanged = Some(self.replace_dead_card());
+    }
+
+    pub fn run_turn(&mut self) -> Option<GameResult> {
+        self.exchange_dead_cards();
+
+        let has_playable_card = self.exchanged.take().unwrap();
         if !has_playable_card {
             LogLevel::Turn.log(&format!("Turn {}: player {} has no playable cards; skipping turn",
                                         self.turn_count, self.up_index));
 
+            let (seat, team) = (self.up_index, Game::player_team(self.num_teams, self.up_index));
+            Game::notify_others(&mut self.players, seat, |player| {
+                player.on_opponent_skip(seat, &team, &self.board)
+            });
+
             self.up_index = (self.up_index + 1) % self.players.len();
 
             return None;
         }
 
-        // choose a card, validate it, remove it from the player's hand, and discard it
-        let (choice_card, choice_square) = self.play_card();
+        // choose a move, validate it, remove its card from the player's hand, and discard it
+        let Some((choice_card, choice_move)) = self.play_card() else {
+            self.up_index = (self.up_index + 1) % self.players.len();
+
+            return None;
+        };
 
-        // place the chip on the board and check victory conditions
-        let result = self.place_chip(choice_card, choice_square);
+        // place or remove the chip on the board and check victory conditions
+        let result = self.place_chip(choice_card, choice_move);
 
-        LogLevel::Board.if_logged(|| self.board.print());
+        LogLevel::Board.if_logged(|| {
+            LogLevel::Board.log(BoardText::new(&self.board, &TextOptions::from_env()).to_string().trim_end())
+        });
+
+        let (seat, team) = (self.up_index, Game::player_team(self.num_teams, self.up_index));
+        Game::notify_others(&mut self.players, seat, |player| {
+            player.on_opponent_move(seat, &team, &choice_card, &choice_move, &self.board)
+        });
 
         // likely moot, but don't finish the turn count when the game is over
         if let Some(winner) = result {
-            return Some(GameResult { winner, turns: self.turn_count });
+            let result = GameResult { winner, turns: self.turn_count };
+            for player in &mut self.players {
+                player.on_game_end(&result);
+            }
+            return Some(result);
         }
 
         // draw a new card
@@ -94,93 +191,98 @@ impl Game {
         None
     }
 
-    // returns true if the player has any playable cards
+    // optionally exchanges dead cards in the player's hand (up to max_dead_card_exchanges of them)
+    // before they choose a card to play; returns true if the player has any playable cards
     fn replace_dead_card(&mut self) -> bool {
-        let hand = &self.player_hands[self.up_index];
-        if hand.iter().any(|card| self.board.is_dead(card)) {
-            let replaced_card_choice = self.up_player().replace_dead_card(&self.board, hand);
-            if let Some(replaced_card_index) = replaced_card_choice {
-                debug_assert!(replaced_card_index <= hand.len(), "dead card choice out of bounds");
-
-                let replaced_card = &self.player_hands[self.up_index].remove(replaced_card_index);
-                debug_assert!(self.board.is_dead(replaced_card), "replaced card was not dead");
+        let team = Game::player_team(self.num_teams, self.up_index);
 
-                self.deck.discard(*replaced_card);
+        for _ in 0..self.max_dead_card_exchanges {
+            if !self.player_hands[self.up_index].iter().any(|card| self.board.is_dead(card)) {
+                break;
+            }
 
-                LogLevel::Turn.log(&format!("Turn {}: player {} replaced dead card {}",
-                                            self.turn_count, self.up_index, replaced_card));
+            let replaced_card_choice = self.choose_dead_card(&team);
+            let Some(replaced_card_index) = replaced_card_choice else { break };
 
-                let new_card = self.deck.draw();
-                self.player_hands[self.up_index].push(new_card);
+            // a choice which is not a dead card in the hand is treated as keeping the cards
+            let hand = &self.player_hands[self.up_index];
+            if !hand.get(replaced_card_index).map_or(false, |card| self.board.is_dead(card)) {
+                LogLevel::Turn.log(&format!("Turn {}: player {} chose card {} which is not dead; keeping it",
+                                            self.turn_count, self.up_index, replaced_card_index));
+                break;
             }
+
+            let replaced_card = &self.player_hands[self.up_index].remove(replaced_card_index);
+
+            self.deck.discard_with_reason(*replaced_card, DiscardReason::Dead);
+
+            let seat = self.up_index;
+            Game::notify_others(&mut self.players, seat, |player| {
+                player.on_opponent_dead_card(seat, &team, replaced_card)
+            });
+
+            LogLevel::Turn.log(&format!("Turn {}: player {} replaced dead card {}",
+                                        self.turn_count, self.up_index, replaced_card));
+
+            let new_card = self.deck.draw();
+            self.player_hands[self.up_index].push(new_card);
         }
 
-        let team = Game::player_team(self.num_teams, self.up_index);
         self.player_hands[self.up_index].iter()
             .any(|card| self.board.can_be_played(card, &team))
     }
 
-    fn play_card(&mut self) -> (Card, Square) {
+    // returns None if the player chooses an illegal move, which forfeits their turn: they keep their
+    // hand, no chip is placed and no card is drawn
+    fn play_card(&mut self) -> Option<(Card, Move)> {
         let player_team = Game::player_team(self.num_teams, self.up_index);
-        let (choice_index, choice_square) = self.up_player().play(
-            &player_team,
-            &self.player_hands[self.up_index],
-            &self.board,
-            &self.deck,
-        );
-
-        debug_assert!(
-            choice_index as usize <= self.player_hands[self.up_index].len(),
-            "card choice out of bounds",
-        );
-        debug_assert!(
-            choice_square.is_playable(),
-            "invalid square choice: {choice_square}",
-        );
+        let choice = self.choose_play(&player_team);
+
+        let choice_index = match choice.validate(&player_team, &self.player_hands[self.up_index], &self.board) {
+            Ok(choice_index) => choice_index,
+            Err(error) => {
+                LogLevel::Turn.log(&format!("Turn {}: player {} chose an illegal move {}: {}; skipping turn",
+                                            self.turn_count, self.up_index, choice, error));
+                return None;
+            }
+        };
 
-        let choice_card = self.player_hands[self.up_index].remove(choice_index as usize);
+        let choice_card = self.player_hands[self.up_index].remove(choice_index);
         self.deck.discard(choice_card);
 
-        (choice_card, choice_square)
+        Some((choice_card, choice))
     }
 
-    fn place_chip(&mut self, card: Card, square: Square) -> Option<Team> {
+    fn place_chip(&mut self, card: Card, choice: Move) -> Option<Team> {
         let player_team = Game::player_team(self.num_teams, self.up_index);
-        if card.is_one_eyed_jack() {
-            debug_assert!(
-                self.board.chip_at(&square).unwrap() != player_team,
-                "attempted to remove chip on own team's square {}", square,
-            );
-
-            self.board.remove_chip(&square);
-            LogLevel::Turn.log(
-                &format!(
-                    "Turn {}: player {} played {}, a one-eyed Jack, and removed the chip on {}",
-                    self.turn_count,
-                    self.up_index,
-                    card,
-                    square,
-                ),
-            );
-        } else {
-            debug_assert!(
-                card.is_two_eyed_jack() || self.board.card_at(&square).unwrap() == card,
-                "attempted to put chip on square not matching the chosen card {}: {}", card, square,
-            );
-
-            LogLevel::Turn.log(
-                &format!(
-                    "Turn {}: player {} played {} on {}",
-                    self.turn_count,
-                    self.up_index,
-                    card,
-                    square,
-                ),
-            );
-
-            if let Some(sequences) = self.board.add_chip(&square, player_team) {
-                if sequences >= Game::winning_sequences(self.num_teams) {
-                    return Some(player_team);
+        match choice {
+            Move::Remove { square } => {
+                self.board.remove_chip(&square);
+                LogLevel::Turn.log(
+                    &format!(
+                        "Turn {}: player {} played {}, a one-eyed Jack, and removed the chip on {}",
+                        self.turn_count,
+                        self.up_index,
+                        card,
+                        square,
+                    ),
+                );
+            }
+            Move::Place { square, .. } | Move::WildPlace { square } => {
+                LogLevel::Turn.log(
+                    &format!(
+                        "Turn {}: player {} played {} on {}",
+                        self.turn_count,
+                        self.up_index,
+                        card,
+                        square,
+                    ),
+                );
+
+                if let Some(sequences) = self.board.add_chip(&square, player_team) {
+                    if sequences >= Game::winning_sequences(self.num_teams) {
+                        return Some(player_team);
+                    }
                 }
             }
         }
@@ -188,8 +290,99 @@ impl Game {
         None
     }
 
-    fn up_player(&self) -> &dyn Player {
-        self.players[self.up_index].as_ref()
+    fn choose_dead_card(&mut self, team: &Team) -> Option<usize> {
+        #[cfg(feature = "analysis")]
+        if let Some(player) = self.privileged_players.get(&self.up_index) {
+            return player.replace_dead_card(&PrivilegedView::new(self, self.up_index));
+        }
+
+        self.players[self.up_index]
+            .replace_dead_card(team, &self.player_hands[self.up_index], &self.board, &self.deck)
+    }
+
+    fn choose_play(&mut self, team: &Team) -> Move {
+        #[cfg(feature = "analysis")]
+        if let Some(player) = self.privileged_players.get(&self.up_index) {
+            return player.play(&PrivilegedView::new(self, self.up_index));
+        }
+
+        self.players[self.up_index]
+            .play(team, &self.player_hands[self.up_index], &self.board, &self.deck)
+    }
+
+    // calls the given function for each player other than the one in the given seat
+    fn notify_others<F>(players: &mut [Box<dyn Player>], seat: usize, mut notify: F)
+        where F: FnMut(&mut dyn Player) {
+        for (index, player) in players.iter_mut().enumerate() {
+            if index != seat {
+                notify(player.as_mut());
+            }
+        }
+    }
+
+    // returns the current state of the board
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    // returns the deck; note that only the public parts of the deck (draw pile size and discard
+    // pile) are exposed
+    pub fn deck(&self) -> &Deck {
+        &self.deck
+    }
+
+    // returns the hand of the player whose turn is next; the hands of the other players are hidden
+    pub fn up_hand(&self) -> &[Card] {
+        self.hand(self.up_index)
+    }
+
+    // returns the hand of the player at the given index, which only the crate itself can see
+    pub(crate) fn hand(&self, player_index: usize) -> &[Card] {
+        &self.player_hands[player_index]
+    }
+
+    // returns the index of the player whose turn is next
+    pub fn up_index(&self) -> usize {
+        self.up_index
+    }
+
+    // returns the number of turns which have been started so far
+    pub fn turn_count(&self) -> usize {
+        self.turn_count
+    }
+
+    // returns the rules of this game, as given to each player at the start of the game
+    pub fn config(&self) -> GameConfig {
+        GameConfig {
+            num_players: self.players.len(),
+            num_teams: self.num_teams,
+            hand_size: Game::hand_size(self.players.len()),
+            winning_sequences: Game::winning_sequences(self.num_teams),
+            max_dead_card_exchanges: self.max_dead_card_exchanges,
+        }
+    }
+
+    pub fn num_players(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn num_teams(&self) -> usize {
+        self.num_teams
+    }
+
+    // gets the team that the player at the given index belongs to in this game
+    pub fn team(&self, player_index: usize) -> Team {
+        Game::player_team(self.num_teams, player_index)
+    }
+
+    // returns the teams playing in this game, in seat order of their first player
+    pub fn teams(&self) -> Vec<Team> {
+        generate_vector(self.num_teams, |index| Game::player_team(self.num_teams, index))
+    }
+
+    // returns the sequences which have been completed by the given team
+    pub fn sequences_for(&self, team: &Team) -> Vec<&HashSet<Square>> {
+        self.board.sequences_for(team).collect()
     }
 
     // gets the team that the player at the given index belongs to
@@ -203,6 +396,14 @@ impl Game {
         }
     }
 
+    // returns true if games with the given number of players and teams are supported, i.e. the
+    // players can be split evenly into teams and hand_size() and winning_sequences() are defined
+    pub fn is_supported(num_players: usize, num_teams: usize) -> bool {
+        matches!(num_teams, 2 | 3) &&
+            num_players % num_teams == 0 &&
+            matches!(num_players, 2 | 3 | 4 | 6 | 8 | 9 | 10 | 12)
+    }
+
     // gets the number of sequences required to win, for the given number of teams
     pub fn winning_sequences(num_teams: usize) -> usize {
         match num_teams {
@@ -230,20 +431,222 @@ impl Game {
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::core::rank::Rank;
+    use crate::core::suit::Suit;
     use crate::players::deterministic_player::DeterministicPlayer;
     use super::*;
 
-    // TODO avoid use of RNG in the game deck
+    // the calls to each hook of a HookRecordingPlayer
+    #[derive(Default)]
+    struct HookCalls {
+        game_start: Option<(usize, Team, GameConfig)>,
+        own_moves: usize,
+        opponent_moves: Vec<(usize, Team)>,
+        game_end: Option<GameResult>,
+    }
+
+    // plays as a DeterministicPlayer, recording the hook calls it receives
+    struct HookRecordingPlayer {
+        calls: Rc<RefCell<HookCalls>>,
+    }
+
+    impl Player for HookRecordingPlayer {
+        fn on_game_start(&mut self, seat: usize, team: &Team, config: &GameConfig) {
+            self.calls.borrow_mut().game_start = Some((seat, *team, *config));
+        }
+
+        fn play(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+            self.calls.borrow_mut().own_moves += 1;
+            DeterministicPlayer {}.play(team, hand, board, deck)
+        }
+
+        fn on_opponent_move(&mut self, seat: usize, team: &Team, _card: &Card, _play: &Move, _board: &Board) {
+            self.calls.borrow_mut().opponent_moves.push((seat, *team));
+        }
+
+        fn on_game_end(&mut self, result: &GameResult) {
+            self.calls.borrow_mut().game_end = Some(*result);
+        }
+    }
+
     #[test]
     fn deterministic_game_runs_without_panics() {
-        for _ in 0..100 {
+        for seed in 0..100 {
             let players: Vec<Box<dyn Player>> = vec![
                 Box::new(DeterministicPlayer {}),
                 Box::new(DeterministicPlayer {}),
             ];
 
-            let mut game = Game::new(players, 2);
+            let mut game = Game::with_seed(players, 2, seed);
             game.run();
         }
     }
+
+    #[test]
+    fn game_runs_without_dead_card_exchanges() {
+        for seed in 0..20 {
+            let players: Vec<Box<dyn Player>> = vec![
+                Box::new(DeterministicPlayer {}),
+                Box::new(DeterministicPlayer {}),
+            ];
+
+            let mut game = Game::with_seed(players, 2, seed);
+            game.set_max_dead_card_exchanges(0);
+
+            // without exchanges a game can stall if every hand is dead, so only play a bounded number
+            // of turns
+            for _ in 0..1000 {
+                if game.run_turn().is_some() {
+                    break;
+                }
+            }
+        }
+    }
+
+    // chooses the given card as its dead card, whether or not it is dead
+    struct DeadCardChoicePlayer {
+        choice: usize,
+    }
+
+    impl Player for DeadCardChoicePlayer {
+        fn play(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+            DeterministicPlayer {}.play(team, hand, board, deck)
+        }
+
+        fn replace_dead_card(&mut self, _team: &Team, _hand: &[Card], _board: &Board, _deck: &Deck) -> Option<usize> {
+            Some(self.choice)
+        }
+    }
+
+    #[test]
+    fn invalid_dead_card_choices_keep_the_cards() {
+        // the first card is live, and the second is dead
+        let hand = [(Rank::Two, Suit::Spades), (Rank::Three, Suit::Spades)].map(|(rank, suit)| Card { rank, suit });
+
+        for choice in [0, 1, 99] {
+            let players: Vec<Box<dyn Player>> = vec![
+                Box::new(DeadCardChoicePlayer { choice }),
+                Box::new(DeterministicPlayer {}),
+            ];
+            let mut game = Game::new(players, 2);
+            game.player_hands[0] = hand.to_vec();
+            for square in game.board.squares_for_card(&hand[1]).unwrap().clone() {
+                game.board.add_chip(&square, Team::Two);
+            }
+
+            game.run_turn();
+            let exchanged = game.deck().discard_pile().contains(&hand[1]);
+            assert_eq!(exchanged, choice == 1, "choice {choice}");
+        }
+    }
+
+    #[test]
+    fn dead_cards_can_be_exchanged_before_the_turn_is_run() {
+        let players: Vec<Box<dyn Player>> = vec![
+            Box::new(DeadCardChoicePlayer { choice: 0 }),
+            Box::new(DeterministicPlayer {}),
+        ];
+        let mut game = Game::with_seed(players, 2, 0);
+        let dead_card = game.player_hands[0][0];
+        for square in game.board.squares_for_card(&dead_card).unwrap().clone() {
+            game.board.add_chip(&square, Team::Two);
+        }
+
+        game.exchange_dead_cards();
+        game.exchange_dead_cards();
+        assert_eq!(game.turn_count(), 1);
+        assert_eq!(game.deck.history(), [(dead_card, DiscardReason::Dead)]);
+        assert_eq!(game.hand(0).len(), 7);
+
+        game.run_turn();
+        assert_eq!(game.turn_count(), 1);
+        assert_eq!(game.deck.history().iter().filter(|(_, reason)| *reason == DiscardReason::Dead).count(), 1);
+        assert_eq!(game.up_index(), 1);
+    }
+
+    // always tries to remove the chip on a corner, which is illegal
+    struct IllegalMovePlayer {}
+
+    impl Player for IllegalMovePlayer {
+        fn play(&mut self, _team: &Team, _hand: &[Card], _board: &Board, _deck: &Deck) -> Move {
+            Move::Remove { square: Square::from_notation("a1") }
+        }
+    }
+
+    #[test]
+    fn illegal_moves_forfeit_the_turn() {
+        let players: Vec<Box<dyn Player>> = vec![
+            Box::new(IllegalMovePlayer {}),
+            Box::new(DeterministicPlayer {}),
+        ];
+        let mut game = Game::new(players, 2);
+        let hand = game.up_hand().to_vec();
+
+        assert!(game.run_turn().is_none());
+        assert_eq!(game.hand(0), hand);
+        assert_eq!(game.up_index(), 1);
+        assert!(game.deck().discard_pile().is_empty());
+    }
+
+    #[test]
+    fn accessors_reflect_state_between_turns() {
+        let players: Vec<Box<dyn Player>> = vec![
+            Box::new(DeterministicPlayer {}),
+            Box::new(DeterministicPlayer {}),
+        ];
+
+        let mut game = Game::new(players, 2);
+        assert_eq!(game.num_players(), 2);
+        assert_eq!(game.num_teams(), 2);
+        assert_eq!(game.teams(), vec![Team::One, Team::Two]);
+        assert_eq!(game.team(0), Team::One);
+        assert_eq!(game.team(1), Team::Two);
+        assert_eq!(game.up_index(), 0);
+        assert_eq!(game.turn_count(), 0);
+        assert_eq!(game.hand(0).len(), 7);
+        assert_eq!(game.hand(1).len(), 7);
+        assert!(game.board().is_empty());
+
+        game.run_turn();
+
+        assert_eq!(game.up_index(), 1);
+        assert_eq!(game.turn_count(), 1);
+        assert_eq!(game.up_hand(), game.hand(1));
+        assert_eq!(game.hand(0).len(), 7);
+        assert!(!game.board().is_empty());
+        assert_eq!(game.deck().discard_pile().len(), 1);
+        assert!(game.sequences_for(&Team::One).is_empty());
+    }
+
+    #[test]
+    fn players_are_notified_of_game_events() {
+        let calls: Vec<Rc<RefCell<HookCalls>>> = generate_vector(2, |_| Rc::default());
+        let players: Vec<Box<dyn Player>> = calls.iter()
+            .map(|calls| Box::new(HookRecordingPlayer { calls: Rc::clone(calls) }) as Box<dyn Player>)
+            .collect();
+
+        let mut game = Game::new(players, 2);
+        let result = game.run();
+
+        let config = game.config();
+        assert_eq!(config.num_players, 2);
+        assert_eq!(config.hand_size, 7);
+        assert_eq!(config.winning_sequences, 2);
+
+        for (seat, calls) in calls.iter().enumerate() {
+            let calls = calls.borrow();
+            let other_seat = 1 - seat;
+
+            assert_eq!(calls.game_start, Some((seat, game.team(seat), config)));
+            assert_eq!(calls.game_end, Some(result));
+            assert!(calls.opponent_moves.iter().all(|event| *event == (other_seat, game.team(other_seat))));
+        }
+
+        // every move by one player is seen by the other
+        assert_eq!(calls[0].borrow().own_moves, calls[1].borrow().opponent_moves.len());
+        assert_eq!(calls[1].borrow().own_moves, calls[0].borrow().opponent_moves.len());
+    }
 }
@@ -1,3 +1,5 @@
+use std::array;
+
 use crate::core::board::{Board, SEQUENCE_LENGTH};
 use crate::core::square::Square;
 use crate::core::team::Team;
@@ -9,9 +11,22 @@ pub const DIRECTIONS: [(i8, i8); 4] = [
     (1i8, -1i8),
 ];
 
+// returns every line of SEQUENCE_LENGTH consecutive squares on the board (in any direction), i.e.
+// every set of squares which could form a sequence
+pub fn windows() -> impl Iterator<Item=[Square; SEQUENCE_LENGTH as usize]> {
+    Square::all_squares()
+        .flat_map(|start| DIRECTIONS.map(|direction| (start, direction)))
+        .filter_map(|(start, (row_delta, col_delta))| {
+            let window: [Square; SEQUENCE_LENGTH as usize] = array::from_fn(|distance| {
+                start.plus(row_delta * distance as i8, col_delta * distance as i8)
+            });
+            if window.iter().all(|square| square.is_valid()) { Some(window) } else { None }
+        })
+}
+
 pub fn runs_for_team(board: &Board, origin: &Square, team: &Team) -> [Vec<Square>; 4] {
     find_runs(origin, SEQUENCE_LENGTH, |square| {
-        board.chip_at(&square).map_or(true, |chip| { chip != *team })
+        board.chip_at(&square) != Some(*team)
     })
 }
 
@@ -85,3 +100,18 @@ pub fn traverse_in_direction<F>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::core::board::BOARD_SIZE;
+
+    use super::*;
+
+    #[test]
+    fn number_of_windows() {
+        // horizontal and vertical: 6 windows per line; diagonals: 6 * 6 in each direction
+        let per_line = (BOARD_SIZE - SEQUENCE_LENGTH + 1) as usize;
+        let expected = 2 * (BOARD_SIZE as usize) * per_line + 2 * per_line * per_line;
+        assert_eq!(windows().count(), expected);
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::square::Square;
+use crate::core::team::Team;
+
+// a move chosen by a player, as returned by Player::play()
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Move {
+    // places a chip on a square with the regular (non-jack) card matching it
+    Place { card: Card, square: Square },
+    // places a chip on any empty square with a two-eyed jack
+    WildPlace { square: Square },
+    // removes the chip of another team (which is not part of a sequence) with a one-eyed jack
+    Remove { square: Square },
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Move::Place { card, square } => write!(f, "{} on {}", card, square),
+            Move::WildPlace { square } => write!(f, "two-eyed jack on {}", square),
+            Move::Remove { square } => write!(f, "one-eyed jack removing {}", square),
+        }
+    }
+}
+
+impl Move {
+    // returns the move which plays the given card on the given square; the kind of move is
+    // determined by the card
+    pub fn for_card(card: Card, square: Square) -> Move {
+        if card.is_one_eyed_jack() {
+            Move::Remove { square }
+        } else if card.is_two_eyed_jack() {
+            Move::WildPlace { square }
+        } else {
+            Move::Place { card, square }
+        }
+    }
+
+    // converts from the legacy form of a move as the index of the card in the hand and the square
+    // on which to play it; None if the index is out of range for the hand
+    pub fn from_index(hand: &[Card], card_index: u8, square: Square) -> Option<Move> {
+        hand.get(card_index as usize).map(|card| Move::for_card(*card, square))
+    }
+
+    // converts to the legacy form of a move as the index of the card in the hand and the square on
+    // which to play it; None if the hand has no card for the move
+    pub fn to_index(&self, hand: &[Card]) -> Option<(u8, Square)> {
+        self.card_index(hand).map(|index| (index as u8, self.square()))
+    }
+
+    pub fn square(&self) -> Square {
+        match self {
+            Move::Place { square, .. } | Move::WildPlace { square } | Move::Remove { square } => *square,
+        }
+    }
+
+    // returns true if the given card can be used to make this move
+    pub fn uses(&self, card: &Card) -> bool {
+        match self {
+            Move::Place { card: place_card, .. } => place_card == card,
+            Move::WildPlace { .. } => card.is_two_eyed_jack(),
+            Move::Remove { .. } => card.is_one_eyed_jack(),
+        }
+    }
+
+    // returns the index of the first card in the given hand which can be used to make this move
+    pub fn card_index(&self, hand: &[Card]) -> Option<usize> {
+        hand.iter().position(|card| self.uses(card))
+    }
+
+    // checks that this move can be made by the given team with the given hand; returns the index of
+    // the card used or a description of why the move is illegal
+    pub fn validate(&self, team: &Team, hand: &[Card], board: &Board) -> Result<usize, String> {
+        let square = self.square();
+        if !square.is_playable() {
+            return Err(format!("square {} is not playable", square));
+        }
+
+        match self {
+            Move::Place { card, .. } => {
+                if card.is_one_eyed_jack() {
+                    return Err(String::from("one-eyed jack cannot place a chip"));
+                }
+                if card.is_two_eyed_jack() {
+                    return Err(String::from("two-eyed jack must be played as a wild placement"));
+                }
+                if board.card_at(&square) != Some(*card) {
+                    return Err(format!("card {} does not match square {} ({})",
+                                       card, square, board.card_at(&square).unwrap()));
+                }
+            }
+            Move::WildPlace { .. } => {}
+            Move::Remove { .. } => {
+                match board.chip_at(&square) {
+                    None => return Err(format!("square {} has no chip to remove", square)),
+                    Some(chip) if chip == *team => {
+                        return Err(format!("cannot remove own team's chip on {}", square));
+                    }
+                    Some(_) => {}
+                }
+                if board.in_sequence(&square) {
+                    return Err(format!("cannot remove chip on {} which is part of a sequence", square));
+                }
+            }
+        }
+
+        if !matches!(self, Move::Remove { .. }) && board.chip_at(&square).is_some() {
+            return Err(format!("square {} already has a chip", square));
+        }
+
+        self.card_index(hand).ok_or_else(|| {
+            match self {
+                Move::Place { card, .. } => format!("card {} is not in hand", card),
+                Move::WildPlace { .. } => String::from("no two-eyed jack in hand"),
+                Move::Remove { .. } => String::from("no one-eyed jack in hand"),
+            }
+        })
+    }
+}
+
+// returns every legal move for the given hand; moves which can be made with more than one card in
+// the hand (i.e. duplicate cards or jacks of the same kind) are only included once
+pub fn legal_moves(team: &Team, hand: &[Card], board: &Board) -> Vec<Move> {
+    let mut seen_moves = HashSet::new();
+    let mut moves = vec![];
+
+    for card in hand {
+        let card_moves: Vec<Move> = if card.is_one_eyed_jack() {
+            Square::playable_squares()
+                .filter(|square| {
+                    board.chip_at(square).map_or(false, |chip| chip != *team) &&
+                        !board.in_sequence(square)
+                })
+                .map(|square| Move::Remove { square })
+                .collect()
+        } else if card.is_two_eyed_jack() {
+            Square::playable_squares()
+                .filter(|square| board.chip_at(square).is_none())
+                .map(|square| Move::WildPlace { square })
+                .collect()
+        } else {
+            board.squares_for_card(card).unwrap_or_default().into_iter()
+                .filter(|square| board.chip_at(square).is_none())
+                .map(|square| Move::Place { card: *card, square })
+                .collect()
+        };
+
+        moves.extend(card_moves.into_iter().filter(|play| seen_moves.insert(*play)));
+    }
+
+    moves
+}
+
+// applies the given move to the board for the given team, without validation; returns the number of
+// sequences owned by the team if new one(s) were created
+pub fn apply_move(board: &mut Board, team: Team, play: &Move) -> Option<usize> {
+    match play {
+        Move::Remove { square } => {
+            board.remove_chip(square);
+            None
+        }
+        Move::Place { square, .. } | Move::WildPlace { square } => board.add_chip(square, team),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::rank::Rank;
+    use crate::core::suit::Suit;
+
+    use super::*;
+
+    #[test]
+    fn legal_moves_for_each_kind_of_card() {
+        let mut board = Board::standard_board();
+        board.add_chip(&Square::from_notation("d1"), Team::One);
+        board.add_chip(&Square::from_notation("e1"), Team::Two);
+
+        let three_of_clubs = Card { rank: Rank::Three, suit: Suit::Clubs };
+        let one_eyed_jack = Card { rank: Rank::Jack, suit: Suit::Spades };
+        let two_eyed_jack = Card { rank: Rank::Jack, suit: Suit::Clubs };
+
+        // d1 is taken, leaving only f3; the duplicate is ignored
+        let f3 = Square::from_notation("f3");
+        let moves = legal_moves(&Team::One, &[three_of_clubs, three_of_clubs], &board);
+        assert_eq!(moves, vec![Move::Place { card: three_of_clubs, square: f3 }]);
+
+        // only the other team's chip can be removed
+        let moves = legal_moves(&Team::One, &[three_of_clubs, one_eyed_jack], &board);
+        assert_eq!(
+            moves,
+            vec![
+                Move::Place { card: three_of_clubs, square: f3 },
+                Move::Remove { square: Square::from_notation("e1") },
+            ],
+        );
+
+        // all empty squares, once each for the two jacks
+        let other_two_eyed_jack = Card { rank: Rank::Jack, suit: Suit::Diamonds };
+        let moves = legal_moves(&Team::One, &[two_eyed_jack, other_two_eyed_jack], &board);
+        assert_eq!(moves.len(), 94);
+    }
+
+    #[test]
+    fn converts_to_and_from_index_form() {
+        let three_of_clubs = Card { rank: Rank::Three, suit: Suit::Clubs };
+        let one_eyed_jack = Card { rank: Rank::Jack, suit: Suit::Spades };
+        let two_eyed_jack = Card { rank: Rank::Jack, suit: Suit::Clubs };
+        let hand = [three_of_clubs, one_eyed_jack, two_eyed_jack];
+        let square = Square::from_notation("f3");
+
+        assert_eq!(Move::from_index(&hand, 0, square), Some(Move::Place { card: three_of_clubs, square }));
+        assert_eq!(Move::from_index(&hand, 1, square), Some(Move::Remove { square }));
+        assert_eq!(Move::from_index(&hand, 2, square), Some(Move::WildPlace { square }));
+        assert_eq!(Move::from_index(&hand, 3, square), None);
+
+        for index in 0..3 {
+            assert_eq!(Move::from_index(&hand, index, square).unwrap().to_index(&hand), Some((index, square)));
+        }
+        assert_eq!(Move::WildPlace { square }.to_index(&[three_of_clubs]), None);
+    }
+
+    #[test]
+    fn validation_describes_illegal_moves() {
+        let mut board = Board::standard_board();
+        board.add_chip(&Square::from_notation("d1"), Team::One);
+        board.add_chip(&Square::from_notation("e1"), Team::Two);
+
+        let three_of_clubs = Card { rank: Rank::Three, suit: Suit::Clubs };
+        let one_eyed_jack = Card { rank: Rank::Jack, suit: Suit::Spades };
+        let hand = [three_of_clubs, one_eyed_jack];
+
+        let f3 = Square::from_notation("f3");
+        assert_eq!(Move::Place { card: three_of_clubs, square: f3 }.validate(&Team::One, &hand, &board), Ok(0));
+        assert_eq!(Move::Remove { square: Square::from_notation("e1") }.validate(&Team::One, &hand, &board), Ok(1));
+
+        let error = |play: Move| play.validate(&Team::One, &hand, &board).unwrap_err();
+        assert_eq!(error(Move::Place { card: one_eyed_jack, square: f3 }), "one-eyed jack cannot place a chip");
+        assert_eq!(error(Move::WildPlace { square: f3 }), "no two-eyed jack in hand");
+        assert_eq!(error(Move::Remove { square: Square::from_notation("d1") }), "cannot remove own team's chip on d1");
+        assert_eq!(error(Move::Remove { square: f3 }), "square f3 has no chip to remove");
+        assert_eq!(
+            error(Move::Place { card: three_of_clubs, square: Square::from_notation("d1") }),
+            "square d1 already has a chip",
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::core::board::{Board, BOARD_SIZE};
+use crate::core::card::Card;
+use crate::core::square::Square;
+use crate::core::team::Team;
+
+// version of the encoding; to be incremented whenever the layout below changes so that exported
+// data from different versions is not mixed up
+pub const ENCODING_VERSION: u32 = 1;
+
+pub const NUM_SQUARES: usize = BOARD_SIZE as usize * BOARD_SIZE as usize;
+pub const NUM_CARDS: usize = 52;
+
+// names of the per-square planes, in the order in which they are encoded
+pub const PLANE_NAMES: [&str; 4] = [
+    "own_chips",
+    "opponent_chips",
+    "in_sequence",
+    "reachable",
+];
+pub const NUM_PLANES: usize = PLANE_NAMES.len();
+
+// names of the scalar values encoded after the planes and the per-card hand counts
+pub const SCALAR_NAMES: [&str; 3] = ["hand_size", "one_eyed_jacks", "two_eyed_jacks"];
+pub const NUM_SCALARS: usize = SCALAR_NAMES.len();
+
+// total length of the flat encoding returned by Observation::encode()
+pub const ENCODING_LENGTH: usize = NUM_PLANES * NUM_SQUARES + NUM_CARDS + NUM_SCALARS;
+
+// a stable encoding of everything a player can see of the position when it is their turn, for use
+// by models trained outside of this crate; the flat encoding is laid out as:
+// - NUM_PLANES planes of NUM_SQUARES values, each indexed by Square::index() (row-major):
+//   - own_chips: 1 if the player's team has a chip on the square, including corners
+//   - opponent_chips: 1 if any other team has a chip on the square
+//   - in_sequence: 1 if the chip on the square is part of a sequence (and so cannot be removed)
+//   - reachable: 1 if the square is empty and has the same card as one in the hand (not counting
+//     two-eyed jacks, which reach every empty square)
+// - NUM_CARDS counts of each card in the hand, indexed by Card::index()
+// - the scalars in SCALAR_NAMES
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Observation {
+    pub planes: [[u8; NUM_SQUARES]; NUM_PLANES],
+    pub hand_counts: [u8; NUM_CARDS],
+    pub hand_size: u8,
+    pub one_eyed_jacks: u8,
+    pub two_eyed_jacks: u8,
+}
+
+impl Observation {
+    pub fn new(team: &Team, hand: &[Card], board: &Board) -> Observation {
+        let mut planes = [[0; NUM_SQUARES]; NUM_PLANES];
+        for square in Square::all_squares() {
+            let index = square.index();
+            match board.chip_at(&square) {
+                Some(chip) if chip == *team => planes[0][index] = 1,
+                Some(_) => planes[1][index] = 1,
+                None => if square.is_corner() { planes[0][index] = 1 },
+            }
+
+            if board.in_sequence(&square) {
+                planes[2][index] = 1;
+            }
+        }
+
+        let mut hand_counts = [0; NUM_CARDS];
+        for card in hand {
+            hand_counts[card.index()] += 1;
+
+            for square in board.squares_for_card(card).unwrap_or_default() {
+                if board.chip_at(&square).is_none() {
+                    planes[3][square.index()] = 1;
+                }
+            }
+        }
+
+        Observation {
+            planes,
+            hand_counts,
+            hand_size: hand.len() as u8,
+            one_eyed_jacks: hand.iter().filter(|card| card.is_one_eyed_jack()).count() as u8,
+            two_eyed_jacks: hand.iter().filter(|card| card.is_two_eyed_jack()).count() as u8,
+        }
+    }
+
+    // returns the flat encoding of this observation, of length ENCODING_LENGTH
+    pub fn encode(&self) -> Vec<u8> {
+        let mut encoding = Vec::with_capacity(ENCODING_LENGTH);
+        for plane in &self.planes {
+            encoding.extend_from_slice(plane);
+        }
+        encoding.extend_from_slice(&self.hand_counts);
+        encoding.extend_from_slice(&[self.hand_size, self.one_eyed_jacks, self.two_eyed_jacks]);
+        encoding
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::rank::Rank;
+    use crate::core::suit::Suit;
+
+    use super::*;
+
+    #[test]
+    fn encodes_chips_sequences_and_hand() {
+        let mut board = Board::standard_board();
+        for square in ["b1", "b2", "b3", "b4", "b5"] {
+            board.add_chip(&Square::from_notation(square), Team::One);
+        }
+        board.add_chip(&Square::from_notation("e4"), Team::Two);
+
+        let card = board.card_at(&Square::from_notation("c1")).unwrap();
+        let hand = vec![
+            card,
+            Card { rank: Rank::Jack, suit: Suit::Spades },
+            Card { rank: Rank::Jack, suit: Suit::Clubs },
+        ];
+        let observation = Observation::new(&Team::Two, &hand, &board);
+        let encoding = observation.encode();
+
+        assert_eq!(encoding.len(), ENCODING_LENGTH);
+        // 4 corners and one chip of our own, five of the opponent's all in a sequence
+        assert_eq!(observation.planes[0].iter().sum::<u8>(), 5);
+        assert_eq!(observation.planes[1].iter().sum::<u8>(), 5);
+        assert_eq!(observation.planes[2].iter().sum::<u8>(), 5);
+        assert_eq!(observation.planes[3][Square::from_notation("c1").index()], 1);
+        assert_eq!(observation.hand_counts[card.index()], 1);
+        assert_eq!(&encoding[ENCODING_LENGTH - NUM_SCALARS..], &[3, 1, 1]);
+    }
+}
@@ -1,22 +1,51 @@
 use crate::core::board::Board;
 use crate::core::card::Card;
 use crate::core::deck::Deck;
-use crate::core::square::Square;
+use crate::core::game::{GameConfig, GameResult};
+use crate::core::moves::Move;
 use crate::core::team::Team;
 
+// a player in a game; players are owned by the Game and may keep state across turns (and games),
+// which is kept up to date by the on_* hooks called by the game (all of which do nothing by default)
 pub trait Player {
-    // return <card index in hand, square on which to play the card>
-    // - for regular cards and two-eyed jacks, claims that square
-    //   - for regular cards, requires that the card matches the square
-    // - for one-eyed jacks, removes the claim on that square
-    //   - requires that the square is claimed by a different team
+    // called once at the start of each game, before any other method, with the seat (index in the
+    // game's players) and team of this player and the configuration of the game
+    fn on_game_start(&mut self, _seat: usize, _team: &Team, _config: &GameConfig) {}
+
+    // return the move to make, which must be legal (see Move::validate()); an illegal move forfeits
+    // the turn, without playing or drawing a card:
+    // - Place claims the square with the regular card in hand matching it
+    // - WildPlace claims any empty square with a two-eyed jack in hand
+    // - Remove removes the claim on a square with a one-eyed jack in hand
+    //   - requires that the square is claimed by a different team and not part of a sequence
     // note that the board and deck are passed as immutable so they cannot be improperly used
-    fn play(&self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> (u8, Square);
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move;
 
-    fn replace_dead_card(&self, board: &Board, hand: &[Card]) -> Option<usize> {
+    // called at the start of the turn (before play()) when the hand contains a dead card; returns
+    // the index of a dead card to exchange for a newly drawn one, or None to keep the hand as is
+    // - may be called again for a further exchange if the game allows more than one per turn
+    // - an index which is not that of a dead card is treated as None
+    fn replace_dead_card(&mut self, _team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> Option<usize> {
         // by default, just pick the first dead card and return it
-        hand.iter()
-            .enumerate()
-            .find_map(|(index, card)| if board.is_dead(card) { Some(index) } else { None })
+        first_dead_card(hand, board)
     }
+
+    // called after each move by another player (including teammates), with their seat and team,
+    // the card they played and the move it was played for, and the board after the move
+    fn on_opponent_move(&mut self, _seat: usize, _team: &Team, _card: &Card, _play: &Move, _board: &Board) {}
+
+    // called after another player exchanges a dead card, with the card they discarded
+    fn on_opponent_dead_card(&mut self, _seat: usize, _team: &Team, _card: &Card) {}
+
+    // called after another player skips their turn because none of their cards can be played on
+    // the given board
+    fn on_opponent_skip(&mut self, _seat: usize, _team: &Team, _board: &Board) {}
+
+    // called once at the end of each game, including for the winning player
+    fn on_game_end(&mut self, _result: &GameResult) {}
+}
+
+// returns the index of the first dead card in the given hand, if any
+pub fn first_dead_card(hand: &[Card], board: &Board) -> Option<usize> {
+    hand.iter().position(|card| board.is_dead(card))
 }
@@ -0,0 +1,62 @@
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::game::Game;
+use crate::core::moves::Move;
+use crate::core::player::first_dead_card;
+use crate::core::team::Team;
+
+// full-information view of a game in progress, including hidden information such as the order of
+// the draw pile and the hands of all players
+// - only available with the "analysis" feature, and only constructed by Game for the player whose
+//   turn it is when that seat has been explicitly assigned a PrivilegedPlayer; regular Player
+//   implementations never receive one
+pub struct PrivilegedView<'a> {
+    game: &'a Game,
+    player_index: usize,
+}
+
+impl<'a> PrivilegedView<'a> {
+    pub(crate) fn new(game: &'a Game, player_index: usize) -> PrivilegedView<'a> {
+        PrivilegedView { game, player_index }
+    }
+
+    pub fn game(&self) -> &Game {
+        self.game
+    }
+
+    // index of the player for whom this view was created, i.e. the player whose turn it is
+    pub fn player_index(&self) -> usize {
+        self.player_index
+    }
+
+    pub fn team(&self) -> Team {
+        self.game.team(self.player_index)
+    }
+
+    pub fn board(&self) -> &Board {
+        self.game.board()
+    }
+
+    // returns the hand of the player at the given index
+    pub fn hand(&self, player_index: usize) -> &[Card] {
+        self.game.hand(player_index)
+    }
+
+    // returns the cards in the draw pile, in reverse order of drawing (i.e. the next card drawn is
+    // the last one); note that when the draw pile is exhausted the discards are shuffled randomly
+    pub fn draw_pile(&self) -> &[Card] {
+        self.game.deck().draw_pile()
+    }
+}
+
+// counterpart of Player which is given a PrivilegedView of the game rather than only the public
+// information and its own hand; see Game::set_privileged_player()
+pub trait PrivilegedPlayer {
+    // see Player::play()
+    fn play(&self, view: &PrivilegedView) -> Move;
+
+    // see Player::replace_dead_card()
+    fn replace_dead_card(&self, view: &PrivilegedView) -> Option<usize> {
+        first_dead_card(view.hand(view.player_index()), view.board())
+    }
+}
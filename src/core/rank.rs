@@ -1,5 +1,7 @@
 use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
 
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 #[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq, PartialOrd, Ord)]
@@ -40,3 +42,12 @@ impl Display for Rank {
         write!(f, "{}", rank_char)
     }
 }
+
+// parses the notation produced by Display, e.g. "T"
+impl FromStr for Rank {
+    type Err = String;
+
+    fn from_str(notation: &str) -> std::result::Result<Self, Self::Err> {
+        Rank::iter().find(|rank| rank.to_string() == notation).ok_or_else(|| format!("invalid rank: {notation}"))
+    }
+}
@@ -0,0 +1,202 @@
+// serde support for the core types, enabled by the "serde" feature
+//
+// cards, ranks, suits, squares and teams are serialized as the strings produced by their Display
+// implementations (e.g. "T♠" and "e4") and parsed back with FromStr; boards are serialized as their
+// cards, chips and completed sequences, and rebuilt with Board::restore() so that their derived
+// indexes are consistent
+//
+// only the public state of decks is serialized, since players could otherwise read the order of the
+// draw pile; they are deserialized with Deck::from_public_state(), and so have an arbitrary draw pile
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::core::board::{Board, BOARD_SIZE};
+use crate::core::card::Card;
+use crate::core::deck::{Deck, DiscardReason};
+use crate::core::rank::Rank;
+use crate::core::square::Square;
+use crate::core::suit::Suit;
+use crate::core::team::Team;
+
+macro_rules! serialize_as_string {
+    ($($type:ty),*) => {
+        $(
+            impl Serialize for $type {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $type {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    parse(&String::deserialize(deserializer)?)
+                }
+            }
+        )*
+    };
+}
+
+serialize_as_string!(Card, Rank, Suit, Square, Team);
+
+fn parse<T, E>(notation: &str) -> Result<T, E> where T: FromStr, T::Err: Display, E: Error {
+    notation.parse().map_err(E::custom)
+}
+
+// the state of a board which is serialized; everything else is derived from it
+#[derive(Deserialize, Serialize)]
+struct BoardState {
+    cards: [[Option<Card>; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+    chips: [[Option<Team>; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+    sequences: Vec<SequenceState>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SequenceState {
+    team: Team,
+    squares: Vec<Square>,
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut chips = [[None; BOARD_SIZE as usize]; BOARD_SIZE as usize];
+        for square in Square::all_squares() {
+            chips[square.row as usize][square.col as usize] = self.chip_at(&square);
+        }
+
+        let sequences = self.sequences().iter()
+            .map(|(team, squares)| {
+                // sorted so that the same board is always serialized the same way
+                let mut squares: Vec<Square> = squares.iter().copied().collect();
+                squares.sort_by_key(|square| square.index());
+                SequenceState { team: *team, squares }
+            })
+            .collect();
+
+        BoardState { cards: *self.cards(), chips, sequences }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = BoardState::deserialize(deserializer)?;
+        let sequences = state.sequences.into_iter()
+            .map(|sequence| (sequence.team, sequence.squares.into_iter().collect::<HashSet<Square>>()))
+            .collect();
+        Board::restore(state.cards, state.chips, sequences).map_err(D::Error::custom)
+    }
+}
+
+// the public state of a deck, as given to Deck::from_public_state(); the discard pile is the end of
+// the history
+#[derive(Deserialize, Serialize)]
+struct DeckState {
+    draw_pile_size: usize,
+    discard_pile: Vec<Card>,
+    history: Vec<(Card, DiscardReason)>,
+    reshuffles: usize,
+}
+
+impl Serialize for Deck {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DeckState {
+            draw_pile_size: self.draw_pile_size(),
+            discard_pile: self.discard_pile().to_vec(),
+            history: self.history().to_vec(),
+            reshuffles: self.reshuffles(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Deck {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = DeckState::deserialize(deserializer)?;
+        let discards = state.history.len().saturating_sub(state.discard_pile.len());
+        if !state.history[discards..].iter().map(|(card, _)| *card).eq(state.discard_pile.iter().copied()) {
+            return Err(D::Error::custom("the discard pile is not the end of the history"));
+        }
+        Deck::from_public_state(state.draw_pile_size, state.reshuffles, state.history, state.discard_pile.len())
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::{Game, GameResult};
+    use crate::players::random_player::RandomPlayer;
+
+    use super::*;
+
+    #[test]
+    fn uses_compact_notation() {
+        let card: Card = "Ts".parse().unwrap();
+        assert_eq!(serde_json::to_string(&card).unwrap(), r#""T♠""#);
+        assert_eq!(serde_json::from_str::<Card>(r#""Ts""#).unwrap(), card);
+        assert_eq!(serde_json::to_string(&card.rank).unwrap(), r#""T""#);
+        assert_eq!(serde_json::to_string(&card.suit).unwrap(), r#""♠""#);
+        assert_eq!(serde_json::from_str::<Square>(r#""e4""#).unwrap(), Square::from_notation("e4"));
+        assert_eq!(serde_json::to_string(&Team::Two).unwrap(), r#""GREEN""#);
+
+        let result = GameResult { winner: Team::One, turns: 42 };
+        assert_eq!(serde_json::to_string(&result).unwrap(), r#"{"winner":"BLUE","turns":42}"#);
+
+        assert!(serde_json::from_str::<Card>(r#""T""#).is_err());
+        assert!(serde_json::from_str::<Square>(r#""k1""#).is_err());
+        assert!(serde_json::from_str::<Team>(r#""PURPLE""#).is_err());
+    }
+
+    #[test]
+    fn round_trips_boards_and_decks() {
+        let mut game = Game::new(vec!(Box::new(RandomPlayer::default()), Box::new(RandomPlayer::default())), 2);
+        let result = game.run();
+
+        let json = serde_json::to_string(game.board()).unwrap();
+        let board: Board = serde_json::from_str(&json).unwrap();
+        for square in Square::all_squares() {
+            assert_eq!(board.card_at(&square), game.board().card_at(&square));
+            assert_eq!(board.chip_at(&square), game.board().chip_at(&square));
+            assert_eq!(board.in_sequence(&square), game.board().in_sequence(&square));
+        }
+        assert_eq!(board.sequences().len(), game.board().sequences().len());
+        assert!(board.sequences_for(&result.winner).count() > 0);
+        let two_of_spades = "2s".parse().unwrap();
+        assert_eq!(board.squares_for_card(&two_of_spades), game.board().squares_for_card(&two_of_spades));
+        assert_eq!(serde_json::to_string(&board).unwrap(), json);
+
+        // a sequence whose chips are missing is rejected
+        let mut empty = serde_json::to_value(Board::standard_board()).unwrap();
+        empty["sequences"] = serde_json::json!([{ "team": "BLUE", "squares": ["b1"] }]);
+        assert!(serde_json::from_value::<Board>(empty).is_err());
+
+        let json = serde_json::to_string(game.deck()).unwrap();
+        let deck: Deck = serde_json::from_str(&json).unwrap();
+        assert_eq!(deck.draw_pile_size(), game.deck().draw_pile_size());
+        assert_eq!(deck.discard_pile(), game.deck().discard_pile());
+        assert_eq!(deck.history(), game.deck().history());
+        assert_eq!(serde_json::to_string(&deck).unwrap(), json);
+
+        // the draw pile is not serialized, only its size
+        let value = serde_json::to_value(game.deck()).unwrap();
+        assert!(value.get("draw_pile").is_none());
+        assert_eq!(value["draw_pile_size"], game.deck().draw_pile_size());
+    }
+
+    #[test]
+    fn rejects_corrupted_boards() {
+        let board = serde_json::to_value(Board::standard_board()).unwrap();
+        let corrupt = |row: usize, col: usize, card: serde_json::Value| {
+            let mut board = board.clone();
+            board["cards"][row][col] = card;
+            serde_json::from_value::<Board>(board).err().unwrap().to_string()
+        };
+
+        assert!(corrupt(0, 0, "2s".into()).contains("corner"));
+        assert!(corrupt(1, 1, serde_json::Value::Null).contains("no card on square"));
+        assert!(corrupt(1, 1, "Js".into()).contains("jack"));
+        let card = board["cards"][1][2].clone();
+        assert!(corrupt(1, 1, card).contains("instead of 2"));
+    }
+}
@@ -1,38 +1,52 @@
 use crate::core::board::Board;
 use crate::core::card::Card;
 use crate::core::deck::Deck;
-use crate::core::player::Player;
+use crate::core::game::GameConfig;
+use crate::core::moves::Move;
+use crate::core::player::{first_dead_card, Player};
 use crate::core::square::Square;
 use crate::core::team::Team;
 
 // convenience wrapper around Player which can be implemented instead to only return the square on
 // which to play
-// this adds logic to find the correct card to play based on the given square (i.e. play a normal
-// card if possible, otherwise the right kind of jack), at the cost of some performance
+// this adds logic to find the correct move based on the given square (i.e. play a normal card if
+// possible, otherwise the right kind of jack), at the cost of some performance
+// - simple players are only told the configuration of each game and are not notified of other game
+//   events; players which need them should implement Player directly
 pub trait SimplePlayer {
+    // see Player::on_game_start()
+    fn on_game_start(&mut self, _config: &GameConfig) {}
+
     fn play_square(&self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Square;
+
+    // see Player::replace_dead_card()
+    fn replace_dead_card(&self, _team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> Option<usize> {
+        first_dead_card(hand, board)
+    }
 }
 
 impl<T> Player for T where T: SimplePlayer {
-    fn play(&self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> (u8, Square) {
+    fn on_game_start(&mut self, _seat: usize, _team: &Team, config: &GameConfig) {
+        SimplePlayer::on_game_start(self, config);
+    }
+
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
         let square = self.play_square(team, hand, board, deck);
 
-        let index = if board.chip_at(&square).is_some() {
-            // if there is a chip on the square, find a one-eyed jack to remove it (assumes that
-            // it is a chip from another team; if not this will be caught by game logic)
-            hand.iter().position(|card| card.is_one_eyed_jack()).unwrap()
+        if board.chip_at(&square).is_some() {
+            // if there is a chip on the square, remove it with a one-eyed jack (assumes that it is
+            // a chip from another team; if not this will be caught by game logic)
+            Move::Remove { square }
         } else {
+            // play a regular card matching the requested square if possible, otherwise a two-eyed
+            // jack
             hand.iter()
-                // find index of regular card which can be played on the requested square
-                .position(|card| {
-                    board.squares_for_card(card).map_or(false, |squares| squares.contains(&square))
-                })
-                // if no such regular card, find the index of a two-eyed jack
-                .unwrap_or_else(|| {
-                    hand.iter().position(|card| card.is_two_eyed_jack()).unwrap()
-                })
-        };
+                .find(|card| board.card_at(&square) == Some(**card))
+                .map_or(Move::WildPlace { square }, |card| Move::Place { card: *card, square })
+        }
+    }
 
-        (index as u8, square)
+    fn replace_dead_card(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Option<usize> {
+        SimplePlayer::replace_dead_card(self, team, hand, board, deck)
     }
 }
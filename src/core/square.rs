@@ -1,4 +1,5 @@
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use itertools::iproduct;
 use crate::core::board::BOARD_SIZE;
 
@@ -15,7 +16,33 @@ impl Display for Square {
     }
 }
 
+// parses the notation produced by Display, e.g. "e4" for column e (the fifth) and row 4
+impl FromStr for Square {
+    type Err = String;
+
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        let mut chars = notation.chars();
+        let (Some(col_char), Some(row_char), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(format!("invalid square: {notation}"));
+        };
+
+        let square = Square {
+            col: (col_char as u32).wrapping_sub('a' as u32) as u8,
+            row: row_char.to_digit(10).map_or(u8::MAX, |row| row as u8),
+        };
+        if !col_char.is_ascii_lowercase() || !square.is_valid() {
+            return Err(format!("invalid square: {notation}"));
+        }
+
+        Ok(square)
+    }
+}
+
 impl Square {
+    pub fn all_squares() -> impl Iterator<Item=Square> {
+        iproduct!(0..BOARD_SIZE, 0..BOARD_SIZE).map(|(row, col)| Square { row, col })
+    }
+
     pub fn playable_squares() -> impl Iterator<Item=Square> {
         iproduct!(0..BOARD_SIZE, 0..BOARD_SIZE)
             .filter_map(|(row, col)| {
@@ -24,6 +51,11 @@ impl Square {
             })
     }
 
+    // returns the position of this square in all_squares(), i.e. a unique index in row-major order
+    pub fn index(&self) -> usize {
+        self.row as usize * BOARD_SIZE as usize + self.col as usize
+    }
+
     pub fn is_valid(&self) -> bool {
         self.row < BOARD_SIZE && self.col < BOARD_SIZE
     }
@@ -70,6 +102,15 @@ mod tests {
         assert_eq!(String::from("a0"), format!("{}", square));
     }
 
+    #[test]
+    fn parse_notation() {
+        assert_eq!("e4".parse(), Ok(Square { row: 4, col: 4 }));
+        assert_eq!("j9".parse(), Ok(Square { row: 9, col: 9 }));
+        assert!("k1".parse::<Square>().is_err());
+        assert!("e".parse::<Square>().is_err());
+        assert!("e10".parse::<Square>().is_err());
+    }
+
     #[test]
     fn convert_e4_back_and_forth_from_notation() {
         let square = Square::from_notation("e4");
@@ -1,4 +1,5 @@
 use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
 
 use strum_macros::EnumIter;
 
@@ -22,3 +23,18 @@ impl Display for Suit {
         write!(f, "{}", suit_char)
     }
 }
+
+// parses the notation produced by Display, e.g. "♠", or an ASCII suit letter (s, h, d or c)
+impl FromStr for Suit {
+    type Err = String;
+
+    fn from_str(notation: &str) -> std::result::Result<Self, Self::Err> {
+        match notation {
+            "s" | "♠" => Ok(Suit::Spades),
+            "h" | "♥" => Ok(Suit::Hearts),
+            "d" | "♦" => Ok(Suit::Diamonds),
+            "c" | "♣" => Ok(Suit::Clubs),
+            _ => Err(format!("invalid suit: {notation}")),
+        }
+    }
+}
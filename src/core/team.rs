@@ -1,5 +1,7 @@
 use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
 
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 #[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq)]
@@ -9,18 +11,6 @@ pub enum Team {
     Three,
 }
 
-impl Team {
-    pub fn with_team_color(&self, string: &str, background: bool) -> String {
-        let color = match self {
-            Team::One => if background { "44" } else { "34" }, // blue
-            Team::Two => if background { "42" } else { "32" }, // green
-            Team::Three => if background { "41" } else { "31" }, // red
-        };
-
-        format!("\x1b[{color}m{string}\x1b[39;49m")
-    }
-}
-
 impl Display for Team {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         let team_name = match self {
@@ -32,3 +22,12 @@ impl Display for Team {
         write!(f, "{}", team_name)
     }
 }
+
+// parses the name produced by Display, e.g. "BLUE"
+impl FromStr for Team {
+    type Err = String;
+
+    fn from_str(name: &str) -> std::result::Result<Self, Self::Err> {
+        Team::iter().find(|team| team.to_string() == name).ok_or_else(|| format!("invalid team: {name}"))
+    }
+}
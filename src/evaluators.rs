@@ -0,0 +1,2 @@
+pub mod linear_evaluator;
+pub mod window_evaluator;
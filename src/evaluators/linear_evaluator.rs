@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+
+use crate::core::board::{Board, SEQUENCE_LENGTH};
+use crate::core::card::Card;
+use crate::core::evaluator::Evaluator;
+use crate::core::grid_traversal::windows;
+use crate::core::square::Square;
+use crate::core::team::Team;
+use crate::util::params::Parameterized;
+
+// names of the features extracted from a position by extract_features(), in order; "opponent"
+// features combine all other teams
+pub const FEATURE_NAMES: [&str; NUM_FEATURES] = [
+    "bias",
+    "own_windows_1",
+    "own_windows_2",
+    "own_windows_3",
+    "own_windows_4",
+    "opponent_windows_1",
+    "opponent_windows_2",
+    "opponent_windows_3",
+    "opponent_windows_4",
+    "own_sequences",
+    "opponent_sequences",
+    "one_eyed_jacks",
+    "two_eyed_jacks",
+    "reachable_squares",
+    "reachable_window_squares",
+];
+
+pub const NUM_FEATURES: usize = 15;
+
+// scale applied to counts of windows and squares so that all features have similar magnitudes,
+// which keeps gradient descent stable
+const COUNT_SCALE: f32 = 0.1;
+
+// extracts the features of the position for the given team, with the hand of its player if known:
+// - the number of windows (lines of SEQUENCE_LENGTH squares) containing exactly n squares counting
+//   for the team (or its opponents) and no chips from any other team, for n = 1..SEQUENCE_LENGTH-1
+// - the number of completed sequences for the team and its opponents
+// - the number of one- and two-eyed jacks in hand
+// - the number of empty squares which can be claimed with a (non-jack) card in hand, and how many
+//   times such squares occur in the team's windows
+pub fn extract_features(board: &Board, team: &Team, hand: Option<&[Card]>) -> [f32; NUM_FEATURES] {
+    let hand = hand.unwrap_or(&[]);
+    let reachable: HashSet<Square> = hand.iter()
+        .flat_map(|card| board.squares_for_card(card))
+        .flatten()
+        .filter(|square| board.chip_at(square).is_none())
+        .collect();
+
+    let mut features = [0.0; NUM_FEATURES];
+    features[0] = 1.0;
+
+    let max_count = SEQUENCE_LENGTH as usize - 1;
+    for window in windows() {
+        let mut owner: Option<Team> = None;
+        let mut corners = 0;
+        let mut chips = 0;
+        let mut reachable_count = 0;
+        let mut contested = false;
+        for square in window {
+            if square.is_corner() {
+                corners += 1;
+            } else if let Some(chip) = board.chip_at(&square) {
+                if owner.map_or(false, |owner| owner != chip) {
+                    contested = true;
+                    break;
+                }
+                owner = Some(chip);
+                chips += 1;
+            } else if reachable.contains(&square) {
+                reachable_count += 1;
+            }
+        }
+
+        // skip windows which are contested, complete, or only contain corners
+        let count = corners + chips;
+        if contested || chips == 0 || count > max_count {
+            continue;
+        }
+
+        if owner == Some(*team) {
+            features[count] += COUNT_SCALE;
+            features[14] += reachable_count as f32 * COUNT_SCALE;
+        } else {
+            features[max_count + count] += COUNT_SCALE;
+        }
+    }
+
+    for (sequence_team, _) in board.sequences() {
+        if sequence_team == team {
+            features[9] += 1.0;
+        } else {
+            features[10] += 1.0;
+        }
+    }
+
+    features[11] = hand.iter().filter(|card| card.is_one_eyed_jack()).count() as f32;
+    features[12] = hand.iter().filter(|card| card.is_two_eyed_jack()).count() as f32;
+    features[13] = reachable.len() as f32 * COUNT_SCALE;
+
+    features
+}
+
+// evaluates a position as a weighted sum of its features (see extract_features()); the weights
+// are typically learned by self-play (see the training module), in which case the sum is the
+// log-odds of the team winning
+pub struct LinearEvaluator {
+    pub weights: [f32; NUM_FEATURES],
+}
+
+impl Default for LinearEvaluator {
+    // hand-written starting point: longer windows are exponentially more valuable, for the team and
+    // (negatively) for its opponents
+    fn default() -> Self {
+        LinearEvaluator {
+            weights: [
+                0.0,
+                0.1, 0.3, 0.9, 2.7,
+                -0.1, -0.3, -0.9, -2.7,
+                3.0, -3.0,
+                0.5, 1.0,
+                0.1, 0.2,
+            ],
+        }
+    }
+}
+
+impl LinearEvaluator {
+    pub fn evaluate_features(&self, features: &[f32; NUM_FEATURES]) -> f32 {
+        self.weights.iter().zip(features).map(|(weight, feature)| weight * feature).sum()
+    }
+
+    // returns the estimated probability that the team wins from the position with the given
+    // features
+    pub fn win_probability(&self, features: &[f32; NUM_FEATURES]) -> f32 {
+        1.0 / (1.0 + (-self.evaluate_features(features)).exp())
+    }
+}
+
+impl Evaluator for LinearEvaluator {
+    fn evaluate(&self, board: &Board, team: &Team, hand: Option<&[Card]>) -> f32 {
+        self.evaluate_features(&extract_features(board, team, hand))
+    }
+}
+
+// each weight is a parameter named after its feature, which allows weights to be saved and loaded
+// with Parameterized::to_param_string() and from_param_file()
+impl Parameterized for LinearEvaluator {
+    fn param_names() -> &'static [&'static str] {
+        &FEATURE_NAMES
+    }
+
+    fn param(&self, name: &str) -> Option<f32> {
+        FEATURE_NAMES.iter().position(|feature| *feature == name).map(|index| self.weights[index])
+    }
+
+    fn set_param(&mut self, name: &str, value: f32) -> Result<(), String> {
+        let index = FEATURE_NAMES.iter().position(|feature| *feature == name)
+            .ok_or_else(|| format!("unknown feature: {name}"))?;
+        self.weights[index] = value;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::rank::Rank;
+    use crate::core::suit::Suit;
+
+    use super::*;
+
+    #[test]
+    fn extract_window_and_hand_features() {
+        let mut board = Board::standard_board();
+        board.add_chip(&Square::from_notation("c4"), Team::One);
+        board.add_chip(&Square::from_notation("d4"), Team::One);
+        board.add_chip(&Square::from_notation("i1"), Team::Two);
+
+        let hand = [
+            Card { rank: Rank::Six, suit: Suit::Hearts },
+            Card { rank: Rank::Jack, suit: Suit::Clubs },
+        ];
+        let features = extract_features(&board, &Team::One, Some(&hand));
+
+        assert_eq!(features[0], 1.0);
+        // c4 and d4 share 3 horizontal windows
+        assert!((features[2] - 0.3).abs() < 1e-6);
+        assert!(features[5] > 0.0);
+        assert_eq!(features[9], 0.0);
+        assert_eq!(features[11], 0.0);
+        assert_eq!(features[12], 1.0);
+        // the 6 of hearts is on d4 (taken) and i5
+        assert!((features[13] - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn weights_round_trip_through_param_string() {
+        let evaluator = LinearEvaluator::default();
+        let loaded = LinearEvaluator::from_param_string(&evaluator.to_param_string()).unwrap();
+        assert_eq!(loaded.weights, evaluator.weights);
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::evaluator::Evaluator;
+use crate::core::grid_traversal::windows;
+use crate::core::square::Square;
+use crate::core::team::Team;
+use crate::util::params::Parameterized;
+
+// evaluates the board by counting windows: every line of SEQUENCE_LENGTH squares which only
+// contains chips from a single team (or corners) could still become a sequence for that team, and
+// is worth (number of squares counting for the team)^window_exponent to it; windows with chips
+// from several teams are worth nothing
+// the score for a team is its completed sequences and windows minus those of all other teams
+pub struct WindowEvaluator {
+    pub sequence_value: f32,
+    pub window_exponent: f32,
+    // value of each empty square in one of the team's windows which can be claimed with a
+    // (non-jack) card in hand, if the hand is known
+    pub reachable_square_value: f32,
+}
+
+impl Default for WindowEvaluator {
+    fn default() -> Self {
+        WindowEvaluator {
+            sequence_value: 10_000.0,
+            window_exponent: 2.0,
+            reachable_square_value: 0.5,
+        }
+    }
+}
+
+impl Evaluator for WindowEvaluator {
+    fn evaluate(&self, board: &Board, team: &Team, hand: Option<&[Card]>) -> f32 {
+        let reachable: HashSet<Square> = hand.unwrap_or(&[]).iter()
+            .flat_map(|card| board.squares_for_card(card))
+            .flatten()
+            .filter(|square| board.chip_at(square).is_none())
+            .collect();
+
+        let mut score = 0.0;
+
+        for (sequence_team, _) in board.sequences() {
+            score += if sequence_team == team { self.sequence_value } else { -self.sequence_value };
+        }
+
+        for window in windows() {
+            let mut owner: Option<Team> = None;
+            let mut count = 0;
+            let mut reachable_count = 0;
+            let mut contested = false;
+            for square in window {
+                if square.is_corner() {
+                    count += 1;
+                } else if let Some(chip) = board.chip_at(&square) {
+                    if owner.map_or(false, |owner| owner != chip) {
+                        contested = true;
+                        break;
+                    }
+                    owner = Some(chip);
+                    count += 1;
+                } else if reachable.contains(&square) {
+                    reachable_count += 1;
+                }
+            }
+
+            if contested {
+                continue;
+            }
+
+            let value = (count as f32).powf(self.window_exponent);
+            match owner {
+                Some(owner) if owner != *team => score -= value,
+                _ => score += value + reachable_count as f32 * self.reachable_square_value,
+            }
+        }
+
+        score
+    }
+}
+
+impl Parameterized for WindowEvaluator {
+    fn param_names() -> &'static [&'static str] {
+        &["sequence_value", "window_exponent", "reachable_square_value"]
+    }
+
+    fn param(&self, name: &str) -> Option<f32> {
+        match name {
+            "sequence_value" => Some(self.sequence_value),
+            "window_exponent" => Some(self.window_exponent),
+            "reachable_square_value" => Some(self.reachable_square_value),
+            _ => None,
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: f32) -> Result<(), String> {
+        match name {
+            "sequence_value" => self.sequence_value = value,
+            "window_exponent" => self.window_exponent = value,
+            "reachable_square_value" => self.reachable_square_value = value,
+            _ => return Err(format!("unknown parameter: {name}")),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::rank::Rank;
+    use crate::core::suit::Suit;
+
+    use super::*;
+
+    #[test]
+    fn evaluation_prefers_own_runs() {
+        let mut board = Board::standard_board();
+        let evaluator = WindowEvaluator::default();
+
+        // only the corner windows count, equally for both teams
+        assert_eq!(
+            evaluator.evaluate(&board, &Team::One, None),
+            evaluator.evaluate(&board, &Team::Two, None),
+        );
+
+        board.add_chip(&Square::from_notation("c4"), Team::One);
+        board.add_chip(&Square::from_notation("d4"), Team::One);
+        let one = evaluator.evaluate(&board, &Team::One, None);
+        let two = evaluator.evaluate(&board, &Team::Two, None);
+        assert!(one > two);
+    }
+
+    #[test]
+    fn reachable_squares_in_hand_add_value() {
+        let mut board = Board::standard_board();
+        let evaluator = WindowEvaluator::default();
+        board.add_chip(&Square::from_notation("c4"), Team::One);
+
+        // the 6 of hearts is on d4, next to c4
+        let hand = [Card { rank: Rank::Six, suit: Suit::Hearts }];
+        assert!(
+            evaluator.evaluate(&board, &Team::One, Some(&hand)) >
+                evaluator.evaluate(&board, &Team::One, None)
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::cell::RefCell;
+use std::io;
+use std::io::Write;
+use std::rc::Rc;
+
+use serde_json::json;
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::Deck;
+use crate::core::game::{Game, GameConfig, GameResult};
+use crate::core::moves::Move;
+use crate::core::observation::{ENCODING_VERSION, Observation};
+use crate::core::player::Player;
+use crate::core::square::Square;
+use crate::core::team::Team;
+
+// a single decision made by a player: what they observed, and the move they chose
+struct Decision {
+    seat: usize,
+    team: Team,
+    observation: Observation,
+    card: Card,
+    square: Square,
+}
+
+// plays two-team games between the players created by the given function and writes a row for
+// every move to the given writer, as JSON lines of the form (with the keys in alphabetical order):
+//   {"card":"T♠","card_index":8,"game":0,"observation":[...],"outcome":1,"ply":0,"seat":0,
+//    "square":"e4","square_index":44,"team":"BLUE","version":1}
+// where observation is the flat Observation::encode() of the mover's view before the move and
+// outcome is 1 if the mover's team went on to win the game and 0 otherwise; returns the number of
+// rows written
+pub fn export_games<F, W>(create_players: F, num_games: usize, writer: &mut W) -> io::Result<usize>
+    where F: Fn() -> Vec<Box<dyn Player>>, W: Write {
+    let mut rows = 0;
+    for game_index in 0..num_games {
+        let (result, decisions) = record_game(create_players());
+
+        for (ply, decision) in decisions.iter().enumerate() {
+            let outcome = if decision.team == result { 1 } else { 0 };
+            writeln!(writer, "{}", to_json_line(game_index, ply, decision, outcome))?;
+            rows += 1;
+        }
+    }
+
+    writer.flush()?;
+    Ok(rows)
+}
+
+// runs a single game, returning the winning team and every decision made, in order; exported games
+// are always between two teams (with the players alternating between them), since the outcome of
+// a row only records whether the mover's team won
+fn record_game(players: Vec<Box<dyn Player>>) -> (Team, Vec<Decision>) {
+    let decisions = Rc::new(RefCell::new(vec![]));
+    let players: Vec<Box<dyn Player>> = players.into_iter()
+        .enumerate()
+        .map(|(seat, player)| {
+            let player = RecordingPlayer { player, seat, decisions: Rc::clone(&decisions) };
+            Box::new(player) as Box<dyn Player>
+        })
+        .collect();
+
+    let result = Game::new(players, 2).run();
+    (result.winner, decisions.take())
+}
+
+fn to_json_line(game_index: usize, ply: usize, decision: &Decision, outcome: u8) -> String {
+    json!({
+        "version": ENCODING_VERSION,
+        "game": game_index,
+        "ply": ply,
+        "seat": decision.seat,
+        "team": decision.team.to_string(),
+        "observation": decision.observation.encode(),
+        "card": decision.card.to_string(),
+        "card_index": decision.card.index(),
+        "square": decision.square.to_string(),
+        "square_index": decision.square.index(),
+        "outcome": outcome,
+    }).to_string()
+}
+
+// wraps a player to record the observation and chosen move of each play; the decisions are shared
+// with the caller since the player itself is owned by the game
+struct RecordingPlayer {
+    player: Box<dyn Player>,
+    seat: usize,
+    decisions: Rc<RefCell<Vec<Decision>>>,
+}
+
+impl Player for RecordingPlayer {
+    fn on_game_start(&mut self, seat: usize, team: &Team, config: &GameConfig) {
+        self.player.on_game_start(seat, team, config);
+    }
+
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+        let play = self.player.play(team, hand, board, deck);
+
+        // an invalid move will be caught by the game
+        if let Some(card_index) = play.card_index(hand) {
+            self.decisions.borrow_mut().push(Decision {
+                seat: self.seat,
+                team: *team,
+                observation: Observation::new(team, hand, board),
+                card: hand[card_index],
+                square: play.square(),
+            });
+        }
+
+        play
+    }
+
+    fn replace_dead_card(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Option<usize> {
+        self.player.replace_dead_card(team, hand, board, deck)
+    }
+
+    fn on_opponent_move(&mut self, seat: usize, team: &Team, card: &Card, play: &Move, board: &Board) {
+        self.player.on_opponent_move(seat, team, card, play, board);
+    }
+
+    fn on_opponent_dead_card(&mut self, seat: usize, team: &Team, card: &Card) {
+        self.player.on_opponent_dead_card(seat, team, card);
+    }
+
+    fn on_opponent_skip(&mut self, seat: usize, team: &Team, board: &Board) {
+        self.player.on_opponent_skip(seat, team, board);
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.player.on_game_end(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::core::observation::ENCODING_LENGTH;
+    use crate::players::random_player::RandomPlayer;
+    use crate::players::square_evaluation_player::SquareEvaluationPlayer;
+
+    use super::*;
+
+    #[test]
+    fn exports_one_row_per_move() {
+        let mut output = vec![];
+        let rows = export_games(
+            || vec!(Box::new(RandomPlayer::default()), Box::new(SquareEvaluationPlayer::default())),
+            2,
+            &mut output,
+        ).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let rows_written: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(rows_written.len(), rows);
+
+        let first = &rows_written[0];
+        assert_eq!(first["version"], 1);
+        assert_eq!(first["game"], 0);
+        assert_eq!(first["ply"], 0);
+        assert_eq!(first["seat"], 0);
+        assert_eq!(first["team"], "BLUE");
+        assert_eq!(first["observation"].as_array().unwrap().len(), ENCODING_LENGTH);
+        assert!(rows_written.iter().all(|row| row["outcome"] == 0 || row["outcome"] == 1));
+    }
+}
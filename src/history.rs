@@ -0,0 +1,191 @@
+use std::io::BufRead;
+
+use serde_json::Value;
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::DiscardReason;
+use crate::core::game::{Game, GameResult};
+use crate::core::moves::{apply_move, Move};
+use crate::core::square::Square;
+use crate::core::team::Team;
+
+// a snapshot of a game at the end of a turn, which can be stepped through
+#[derive(Clone)]
+pub struct Frame {
+    pub board: Board,
+    pub turn: usize,
+    // a description of the turn, e.g. "Turn 3: BLUE (seat 0) played T♠ on e4"
+    pub event: String,
+    // the square whose chip was placed or removed in the turn, if any
+    pub square: Option<Square>,
+    // the hand of the human player at the end of the turn, if there is one
+    pub hand: Vec<Card>,
+    pub result: Option<GameResult>,
+}
+
+impl Frame {
+    // the frame for the start of the given game
+    pub fn start(game: &Game, human_seat: Option<usize>) -> Frame {
+        Frame {
+            board: game.board().clone(),
+            turn: 0,
+            event: String::from("Game started"),
+            square: None,
+            hand: human_seat.map_or(vec![], |seat| game.hand(seat).to_vec()),
+            result: None,
+        }
+    }
+
+    // the frame for the end of the turn just taken in the given game by the player in the given seat,
+    // given the board and the length of the deck's history before the turn
+    pub fn after_turn(
+        game: &Game,
+        seat: usize,
+        board_before: &Board,
+        history_before: usize,
+        human_seat: Option<usize>,
+        result: Option<GameResult>,
+    ) -> Frame {
+        let team = game.team(seat);
+        let square = Square::all_squares().find(|square| board_before.chip_at(square) != game.board().chip_at(square));
+
+        let mut actions: Vec<String> = vec![];
+        for (card, reason) in &game.deck().history()[history_before..] {
+            actions.push(match (reason, square) {
+                (DiscardReason::Dead, _) => format!("exchanged dead card {card}"),
+                (DiscardReason::Played, Some(square)) if game.board().chip_at(&square).is_none() => {
+                    format!("removed the chip on {square} with {card}")
+                }
+                (DiscardReason::Played, Some(square)) => format!("played {card} on {square}"),
+                (DiscardReason::Played, None) => format!("played {card}"),
+            });
+        }
+        if square.is_none() {
+            actions.push(String::from("skipped their turn"));
+        }
+
+        let mut event = format!("Turn {}: {} (seat {}) {}", game.turn_count(), team, seat, actions.join(", then "));
+        if result.is_some() {
+            event.push_str(" and won");
+        }
+
+        Frame {
+            board: game.board().clone(),
+            turn: game.turn_count(),
+            event,
+            square,
+            hand: human_seat.map_or(vec![], |seat| game.hand(seat).to_vec()),
+            result,
+        }
+    }
+}
+
+// loads the frames of the game with the given index from training data written by
+// exporter::export_games(), which records every move (but not dead cards or skipped turns, so these
+// do not have frames) and whether the mover's team went on to win; the game must be complete, i.e.
+// end with the winner's last sequence
+pub fn load_export<R: BufRead>(reader: R, game_index: usize) -> Result<Vec<Frame>, String> {
+    let mut frames = vec![Frame {
+        board: Board::standard_board(),
+        turn: 0,
+        event: String::from("Game started"),
+        square: None,
+        hand: vec![],
+        result: None,
+    }];
+    let mut winner: Option<Team> = None;
+
+    for line in reader.lines() {
+        let line = line.map_err(|error| error.to_string())?;
+        let row: Value = serde_json::from_str(&line).map_err(|error| format!("invalid row: {error}"))?;
+        if row["game"].as_u64() != Some(game_index as u64) {
+            continue;
+        }
+
+        let field = |name: &str| row[name].as_str().ok_or_else(|| format!("missing {name} in row: {line}"));
+        let team: Team = field("team")?.parse()?;
+        let card: Card = field("card")?.parse()?;
+        let square: Square = field("square")?.parse()?;
+        if row["outcome"].as_u64() == Some(1) {
+            winner = Some(team);
+        }
+
+        let mut board = frames.last().unwrap().board.clone();
+        let play = Move::for_card(card, square);
+        play.validate(&team, &[card], &board)?;
+        apply_move(&mut board, team, &play);
+
+        let turn = row["ply"].as_u64().unwrap_or_default() as usize + 1;
+        let seat = row["seat"].as_u64().unwrap_or_default();
+        let action = match play {
+            Move::Remove { .. } => format!("removed the chip on {square} with {card}"),
+            Move::Place { .. } | Move::WildPlace { .. } => format!("played {card} on {square}"),
+        };
+        frames.push(Frame {
+            board,
+            turn,
+            event: format!("Move {turn}: {team} (seat {seat}) {action}"),
+            square: Some(square),
+            hand: vec![],
+            result: None,
+        });
+    }
+
+    if frames.len() == 1 {
+        return Err(format!("no moves found for game {game_index}"));
+    }
+
+    let winner = winner.ok_or_else(|| format!("no winner recorded for game {game_index}"))?;
+    // exported games are always between two teams
+    let won = |frame: &Frame| frame.board.sequences_for(&winner).count() >= Game::winning_sequences(2);
+    let Some(last) = frames.last_mut().filter(|last| won(last)) else {
+        return Err(format!("game {game_index} ends before {winner} won; is the file truncated?"));
+    };
+    last.event.push_str(" and won");
+    last.result = Some(GameResult { winner, turns: last.turn });
+
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::exporter::export_games;
+    use crate::players::random_player::RandomPlayer;
+
+    use super::*;
+
+    #[test]
+    fn loads_exported_games() {
+        let mut output = vec![];
+        let rows = export_games(
+            || vec!(Box::new(RandomPlayer::default()), Box::new(RandomPlayer::default())),
+            2,
+            &mut output,
+        ).unwrap();
+
+        let first = load_export(Cursor::new(&output), 0).unwrap();
+        let second = load_export(Cursor::new(&output), 1).unwrap();
+        assert_eq!(first.len() + second.len() - 2, rows);
+        assert!(first[0].board.is_empty());
+        assert!(first.last().unwrap().result.is_some());
+        assert!(first[1].event.starts_with("Move 1: BLUE (seat 0) played"));
+        // one-eyed jacks (and only they) remove chips
+        for frame in first.iter().chain(&second).skip(1) {
+            let one_eyed_jack = frame.event.contains("J♠") || frame.event.contains("J♥");
+            assert_eq!(frame.event.contains("removed the chip on"), one_eyed_jack, "{}", frame.event);
+        }
+
+        assert!(load_export(Cursor::new(&output), 2).is_err());
+
+        // a game cut off before its last move, and one with no winner recorded
+        let lines: Vec<&str> = std::str::from_utf8(&output).unwrap().lines().collect();
+        let truncated = lines[..first.len() - 2].join("\n");
+        let error = load_export(Cursor::new(truncated), 0).err().unwrap();
+        assert!(error.contains("truncated"), "{error}");
+        let no_winner = lines[..first.len() - 1].join("\n").replace("\"outcome\":1", "\"outcome\":0");
+        assert!(load_export(Cursor::new(no_winner), 0).err().unwrap().contains("no winner"));
+    }
+}
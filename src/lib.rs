@@ -0,0 +1,43 @@
+// the Sequence engine and bundled players, along with the front-ends built on them (the bot
+// protocol, TCP and HTTP servers, terminal interface and renderers), for use by other crates; the
+// types most users need are re-exported here, e.g. to play a game:
+//
+//     let players: Vec<Box<dyn Player>> = vec![
+//         Box::new(RandomPlayer::default()),
+//         Box::new(RandomPlayer::default()),
+//     ];
+//     let result = Game::new(players, 2).run();
+pub mod core;
+pub mod evaluators;
+pub mod exporter;
+pub mod history;
+pub mod log;
+pub mod players;
+pub mod protocol;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod render;
+pub mod server;
+pub mod trainer;
+#[cfg(all(feature = "tui", not(target_arch = "wasm32")))]
+pub mod tui;
+pub mod tuner;
+pub mod util;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+#[cfg(all(feature = "web", not(target_arch = "wasm32")))]
+pub mod web;
+
+pub use crate::core::board::Board;
+pub use crate::core::card::Card;
+pub use crate::core::deck::Deck;
+pub use crate::core::game::{Game, GameConfig, GameResult};
+pub use crate::core::moves::Move;
+pub use crate::core::player::Player;
+pub use crate::core::rank::Rank;
+pub use crate::core::square::Square;
+pub use crate::core::suit::Suit;
+pub use crate::core::team::Team;
+pub use crate::players::random_player::RandomPlayer;
+pub use crate::players::square_evaluation_player::SquareEvaluationPlayer;
+pub use crate::players::{create_bot, BOT_NAMES};
@@ -1,5 +1,8 @@
+use std::sync::RwLock;
 use std::time;
-use crate::LOG_OPTIONS;
+
+// the options for all logging in this process; nothing is logged until they are set
+static LOG_OPTIONS: RwLock<LogOptions> = RwLock::new(LogOptions::NONE);
 
 pub struct LogOptions {
     pub level: LogLevel,
@@ -11,10 +14,12 @@ pub struct LogOptions {
     pub every_n: Option<usize>,
     // log results after each percent of the total games is complete, 0 <= percent <= 100
     pub every_percent: Option<f32>,
+
+    // where each line is logged, e.g. print_line for stdout (which does not exist in a browser)
+    pub output: fn(&str),
 }
 
 pub enum LogLevel {
-    #[allow(dead_code)]
     None,
 
     // log nothing from each game
@@ -25,13 +30,27 @@ pub enum LogLevel {
     Board, // log the board after each turn
 }
 
+impl LogOptions {
+    pub const NONE: LogOptions = LogOptions {
+        level: LogLevel::None,
+        every_n: None,
+        every_percent: None,
+        output: print_line,
+    };
+
+    // sets the options for all logging in this process, e.g. at the start of main()
+    pub fn set(options: LogOptions) {
+        *LOG_OPTIONS.write().unwrap() = options;
+    }
+}
+
 impl LogLevel {
     pub fn log(&self, message: &str) {
-        self.if_logged(|| println!("{}", message));
+        self.if_logged(|| (LOG_OPTIONS.read().unwrap().output)(message));
     }
 
     pub fn if_logged<F>(&self, block: F) where F: FnOnce() {
-        if LOG_OPTIONS.level.ord() >= self.ord() {
+        if LOG_OPTIONS.read().unwrap().level.ord() >= self.ord() {
             block();
         }
     }
@@ -40,9 +59,10 @@ impl LogLevel {
         LogLevel::Results.if_logged(|| {
             // TODO log result every time if the log level is strictly greater than only logging
             //  results
-            let log_result = LOG_OPTIONS.every_n
+            let options = LOG_OPTIONS.read().unwrap();
+            let log_result = options.every_n
                 .map(|n| n as f32)
-                .or(LOG_OPTIONS.every_percent.map(|p| { (p / 100.0) * total as f32 }))
+                .or(options.every_percent.map(|p| { (p / 100.0) * total as f32 }))
                 .map(|n| {
                     // TODO doesn't work quite correctly for non-integer n
                     game % (n as usize) == 0
@@ -53,8 +73,8 @@ impl LogLevel {
                 let percent = (game as f32 / total as f32) * 100.0;
                 // TODO improve formatting of the duration
                 // TODO print expected remaining time
-                println!("Finished game {}/{} ({:.2}%) in {:?}",
-                         game, total, percent, start.elapsed());
+                (options.output)(&format!("Finished game {}/{} ({:.2}%) in {:?}",
+                                          game, total, percent, start.elapsed()));
             }
         });
     }
@@ -68,3 +88,8 @@ impl LogLevel {
         }
     }
 }
+
+// logs to stdout
+pub fn print_line(message: &str) {
+    println!("{}", message);
+}
@@ -1,33 +1,162 @@
 use std::collections::HashMap;
+use std::env;
+use std::fs::{self, File};
+use std::io;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
 use std::time::Instant;
 
-use crate::core::game::Game;
-use crate::core::player::Player;
-use crate::log::{LogLevel, LogOptions};
-use crate::players::random_player::RandomPlayer;
-use crate::players::square_evaluation_player::SquareEvaluationPlayer;
-
-pub mod core;
-mod log;
-mod players;
-pub mod util;
+use sequence::core::game::Game;
+use sequence::core::player::Player;
+use sequence::evaluators::linear_evaluator::LinearEvaluator;
+use sequence::exporter::export_games;
+use sequence::history::{load_export, Frame};
+use sequence::log::{print_line, LogLevel, LogOptions};
+use sequence::players::expectimax_player::ExpectimaxPlayer;
+use sequence::players::learned_player::LearnedPlayer;
+#[cfg(feature = "analysis")]
+use sequence::players::oracle_player::OraclePlayer;
+use sequence::players::process_player::ProcessPlayer;
+use sequence::players::random_player::RandomPlayer;
+#[cfg(not(target_arch = "wasm32"))]
+use sequence::players::{create_bot, BOT_NAMES};
+use sequence::players::square_evaluation_player::SquareEvaluationPlayer;
+use sequence::protocol::serve;
+use sequence::render::svg::{game_html, game_svgs};
+use sequence::server::Server;
+use sequence::trainer::{Trainer, TrainerOptions};
+#[cfg(not(target_arch = "wasm32"))]
+use sequence::tui::{self, Mode};
+use sequence::tuner::{ParamRange, Tuner, TunerOptions, TuningOpponent};
+use sequence::util::params::Parameterized;
+#[cfg(not(target_arch = "wasm32"))]
+use sequence::web::WebServer;
 
 const N: usize = 100;
+const LEARNED_WEIGHTS_FILE: &str = "learned_weights.params";
+const TRAINING_DATA_FILE: &str = "training_data.jsonl";
+const RENDERED_GAME_FILE: &str = "game.html";
+const SERVER_ADDRESS: &str = "127.0.0.1:7878";
+#[cfg(not(target_arch = "wasm32"))]
+const WEB_ADDRESS: &str = "127.0.0.1:8080";
 const LOG_OPTIONS: LogOptions = LogOptions {
     level: LogLevel::Results,
     every_n: None,
     every_percent: Some(5.0),
+    output: print_line,
 };
 
+// usage:
+// - no arguments: simulate N games and print the number of wins for each team
+// - expectimax: simulate N games of SquareEvaluationPlayer against ExpectimaxPlayer
+// - oracle (requires the "analysis" feature): simulate N games of OraclePlayer against
+//   SquareEvaluationPlayer
+// - tune [baseline|previous-best]: tune the parameters of SquareEvaluationPlayer, either against
+//   a default SquareEvaluationPlayer (the default) or against the best parameters found so far
+// - train: learn the weights of a LearnedPlayer by self-play, writing them to LEARNED_WEIGHTS_FILE
+// - learned: simulate N games of SquareEvaluationPlayer against a LearnedPlayer with the weights in
+//   LEARNED_WEIGHTS_FILE
+// - export [file]: simulate N games of SquareEvaluationPlayer against a LearnedPlayer with its
+//   default weights and write every move, with the mover's observation and the final outcome, to
+//   the given file (TRAINING_DATA_FILE by default) as JSON lines
+// - bot: run a SquareEvaluationPlayer as a bot over stdin/stdout, speaking the protocol in
+//   protocol.rs
+// - process <command> [args...]: simulate N games of SquareEvaluationPlayer against a bot launched
+//   with the given command (e.g. a bot written in another language, or this binary's bot mode)
+// - server [address] [players] [teams]: host a single game for clients connecting over TCP to the
+//   given address (SERVER_ADDRESS by default), speaking the JSON protocol in server.rs; 2 players in
+//   2 teams by default
+// - web [address]: serve the HTTP API in web.rs, and a page using it to play against
+//   SquareEvaluationPlayer in a browser, on the given address (WEB_ADDRESS by default)
+// - tui [bot]: play against the given bot (see players::BOT_NAMES; square_evaluation by default) in
+//   a full-screen terminal interface
+// - watch [bot] [bot]: watch two bots play each other in the terminal interface
+// - replay [file] [game]: step through the game with the given index (0 by default) in training
+//   data written by the export mode to the given file (TRAINING_DATA_FILE by default)
+// - render [file] [game] [output]: render the game loaded as in the replay mode as a single HTML
+//   page with a slider (if the output ends in .html, as RENDERED_GAME_FILE does by default) or as
+//   one SVG image per frame in the output directory
+// the web, tui, watch and replay modes are not available when compiled to WebAssembly (where the
+// library is meant to be used through wasm.rs instead)
 fn main() {
+    LogOptions::set(LOG_OPTIONS);
+
+    let args: Vec<String> = env::args().collect();
+    match args.get(1).map(|arg| arg.as_str()) {
+        Some("tune") => tune(args.get(2).map(|arg| arg.as_str())),
+        Some("train") => train(),
+        Some("bot") => serve(
+            &mut SquareEvaluationPlayer::default(),
+            "SquareEvaluationPlayer",
+            io::stdin().lock(),
+            &mut io::stdout().lock(),
+        ).expect("bot failed"),
+        Some("process") => {
+            let command = args.get(2).expect("missing bot command");
+            // launch the bot once up front to check that it works
+            let bot = ProcessPlayer::spawn(command, &args[3..]).expect("unable to launch bot");
+            println!("Playing against bot {:?}", bot.name());
+            drop(bot);
+
+            simulate(|| vec!(
+                Box::new(SquareEvaluationPlayer::default()),
+                Box::new(ProcessPlayer::spawn(command, &args[3..]).expect("unable to launch bot")),
+            ))
+        }
+        Some("server") => host(
+            args.get(2).map_or(SERVER_ADDRESS, |arg| arg.as_str()),
+            args.get(3).map_or(2, |arg| arg.parse().expect("invalid number of players")),
+            args.get(4).map_or(2, |arg| arg.parse().expect("invalid number of teams")),
+        ),
+        #[cfg(not(target_arch = "wasm32"))]
+        Some("web") => {
+            let server = WebServer::bind(args.get(2).map_or(WEB_ADDRESS, |arg| arg.as_str()))
+                .expect("unable to start web server");
+            println!("Serving on http://{}", server.local_addr().unwrap());
+            server.run().expect("web server failed");
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        Some("tui") => {
+            let bot = args.get(2).map_or("square_evaluation", |arg| arg.as_str());
+            check_bot(bot);
+            tui::play(Mode::Play(0), vec![bot.to_string()]).expect("terminal interface failed");
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        Some("watch") => {
+            let bots: Vec<String> = (2..4)
+                .map(|i| args.get(i).map_or("square_evaluation", |arg| arg.as_str()).to_string())
+                .collect();
+            bots.iter().for_each(|bot| check_bot(bot));
+            tui::play(Mode::Watch, bots).expect("terminal interface failed");
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        Some("replay") => tui::replay(load_game(&args)).expect("terminal interface failed"),
+        Some("render") => render(&args),
+        Some("export") => export(args.get(2).map_or(TRAINING_DATA_FILE, |arg| arg.as_str())),
+        Some("learned") => simulate(|| vec!(
+            Box::new(SquareEvaluationPlayer::default()),
+            Box::new(LearnedPlayer::from_param_file(LEARNED_WEIGHTS_FILE).unwrap()),
+        )),
+        #[cfg(feature = "analysis")]
+        Some("oracle") => simulate_oracle(),
+        Some("expectimax") => simulate(|| vec!(
+            Box::new(SquareEvaluationPlayer::default()),
+            Box::new(ExpectimaxPlayer::default()),
+        )),
+        _ => simulate(|| vec!(
+            Box::new(RandomPlayer::default()),
+            Box::new(SquareEvaluationPlayer { ..Default::default() }),
+        )),
+    }
+}
+
+// simulates N games between the players created by the given function
+fn simulate<F>(create_players: F) where F: Fn() -> Vec<Box<dyn Player>> {
     let start = Instant::now();
     let mut winners = HashMap::new();
 
     for i in 0..N {
-        let players: Vec<Box<dyn Player>> = vec!(
-            Box::new(RandomPlayer {}),
-            Box::new(SquareEvaluationPlayer { ..Default::default() }),
-        );
+        let players = create_players();
 
         let result = Game::new(players, 2).run();
         winners.entry(result.winner).and_modify(|count| *count += 1).or_insert(1);
@@ -41,3 +170,135 @@ fn main() {
         println!("{} : {}", team, win_count);
     }
 }
+
+// exits with the names of the available bots if the given one does not exist
+#[cfg(not(target_arch = "wasm32"))]
+fn check_bot(name: &str) {
+    if create_bot(name).is_none() {
+        eprintln!("Unknown bot {:?}; choose from {}", name, BOT_NAMES.join(", "));
+        std::process::exit(1);
+    }
+}
+
+// hosts a single game for remote clients and prints its result
+fn host(address: &str, num_players: usize, num_teams: usize) {
+    let server = Server::bind(address, num_players, num_teams).expect("unable to start server");
+    println!("Waiting for {} players on {}", num_players, server.local_addr().unwrap());
+
+    let result = server.run().expect("server failed");
+    println!("{} won in {} turns", result.winner, result.turns);
+}
+
+// simulates games between an OraclePlayer (in alternating seats) and a SquareEvaluationPlayer
+#[cfg(feature = "analysis")]
+fn simulate_oracle() {
+    let start = Instant::now();
+    let mut oracle_wins = 0;
+
+    for i in 0..N {
+        let players: Vec<Box<dyn Player>> = vec!(
+            Box::new(SquareEvaluationPlayer::default()),
+            Box::new(SquareEvaluationPlayer::default()),
+        );
+
+        let oracle_index = i % 2;
+        let mut game = Game::new(players, 2);
+        game.set_privileged_player(oracle_index, Box::new(OraclePlayer::default()));
+
+        let result = game.run();
+        if result.winner == game.team(oracle_index) {
+            oracle_wins += 1;
+        }
+
+        LogLevel::on_result(i + 1, N, &start);
+    }
+
+    LogLevel::Results.log("");
+    println!("Done in {:?}!", start.elapsed());
+    println!("Oracle won {} of {} games", oracle_wins, N);
+}
+
+fn tune(opponent: Option<&str>) {
+    let start = Instant::now();
+
+    let space = vec![
+        ParamRange { name: "two_eyed_jack_cutoff", min: 0.0, max: 200.0 },
+        ParamRange { name: "one_eyed_jack_cutoff", min: 0.0, max: 200.0 },
+        ParamRange { name: "owned_square_value", min: 0.0, max: 20.0 },
+        ParamRange { name: "reachable_square_value", min: 0.0, max: 10.0 },
+        ParamRange { name: "other_square_value", min: 0.0, max: 5.0 },
+        ParamRange { name: "distance_falloff", min: 0.0, max: 0.5 },
+        ParamRange { name: "defense_weight", min: 0.0, max: 5.0 },
+        ParamRange { name: "defense_exponent", min: 1.0, max: 4.0 },
+        ParamRange { name: "defense_min_chips", min: 1.0, max: 4.0 },
+    ];
+
+    let opponent = match opponent {
+        Some("previous-best") => TuningOpponent::PreviousBest(|player: SquareEvaluationPlayer| {
+            Box::new(player) as Box<dyn Player>
+        }),
+        _ => TuningOpponent::Baseline(Box::new(|| Box::new(SquareEvaluationPlayer::default()))),
+    };
+
+    let mut tuner = Tuner::new(space, opponent, TunerOptions::default(), SquareEvaluationPlayer::default());
+    tuner.run();
+
+    tuner.write_best("tuning_best.params").expect("unable to write best parameters");
+    tuner.write_log("tuning_log.tsv").expect("unable to write tuning log");
+
+    println!("Evaluated {} parameter sets in {:?}!", tuner.log().len(), start.elapsed());
+    println!("Best win rate {} with {}", tuner.best_win_rate(), tuner.best().to_param_string());
+}
+
+fn train() {
+    let start = Instant::now();
+
+    let mut trainer = Trainer::new(TrainerOptions::default(), LinearEvaluator::default());
+    trainer.run();
+    trainer.write_weights(LEARNED_WEIGHTS_FILE).expect("unable to write learned weights");
+
+    println!("Done in {:?}!", start.elapsed());
+    println!("Win rates against SquareEvaluationPlayer by generation: {:?}", trainer.benchmarks());
+    println!("Learned weights: {}", trainer.evaluator().to_param_string());
+}
+
+// loads the game given by the arguments of the replay and render modes: the training data file
+// and the index of the game in it
+fn load_game(args: &[String]) -> Vec<Frame> {
+    let file = File::open(args.get(2).map_or(TRAINING_DATA_FILE, |arg| arg.as_str()))
+        .expect("unable to open training data");
+    let game = args.get(3).map_or(0, |arg| arg.parse().expect("invalid game index"));
+    load_export(BufReader::new(file), game).expect("unable to load game")
+}
+
+fn render(args: &[String]) {
+    let frames = load_game(args);
+    let output = Path::new(args.get(4).map_or(RENDERED_GAME_FILE, |arg| arg.as_str()));
+
+    if output.extension().map_or(false, |extension| extension == "html") {
+        fs::write(output, game_html("Sequence", &frames)).expect("unable to write page");
+    } else {
+        fs::create_dir_all(output).expect("unable to create output directory");
+        for (i, svg) in game_svgs(&frames).iter().enumerate() {
+            fs::write(output.join(format!("frame-{i:03}.svg")), svg).expect("unable to write image");
+        }
+    }
+
+    println!("Rendered {} frames to {}", frames.len(), output.display());
+}
+
+fn export(path: &str) {
+    let start = Instant::now();
+
+    let mut writer = BufWriter::new(File::create(path).expect("unable to create training data file"));
+    let rows = export_games(
+        || vec!(
+            Box::new(SquareEvaluationPlayer::default()),
+            Box::new(LearnedPlayer::default()),
+        ),
+        N,
+        &mut writer,
+    ).expect("unable to write training data");
+
+    println!("Wrote {} rows from {} games to {} in {:?}!", rows, N, path, start.elapsed());
+}
@@ -1,3 +1,41 @@
+use crate::core::player::Player;
+use crate::players::expectimax_player::ExpectimaxPlayer;
+use crate::players::random_player::RandomPlayer;
+use crate::players::square_evaluation_player::SquareEvaluationPlayer;
+
 pub mod deterministic_player;
+pub mod expectimax_player;
+pub mod learned_player;
+#[cfg(feature = "analysis")]
+pub mod oracle_player;
+pub mod process_player;
 pub mod random_player;
 pub mod square_evaluation_player;
+
+// the bots which can be chosen by name, e.g. through the web API or in the terminal UI; the first
+// is the default
+pub const BOT_NAMES: [&str; 3] = ["square_evaluation", "expectimax", "random"];
+
+// creates the bot with the given name, if it is one of BOT_NAMES
+pub fn create_bot(name: &str) -> Option<Box<dyn Player>> {
+    match name {
+        "square_evaluation" => Some(Box::new(SquareEvaluationPlayer::default())),
+        "expectimax" => {
+            // a shallower search than the default, so that a bot's turn does not keep a player waiting
+            let mut player = ExpectimaxPlayer::default();
+            player.depth = 2;
+            Some(Box::new(player))
+        }
+        "random" => Some(Box::new(RandomPlayer::default())),
+        _ => None,
+    }
+}
+
+// creates the bot with the given name as create_bot() does, with any random choices it makes
+// determined by the given seed
+pub fn create_seeded_bot(name: &str, seed: u64) -> Option<Box<dyn Player>> {
+    match name {
+        "random" => Some(Box::new(RandomPlayer::with_seed(seed))),
+        _ => create_bot(name),
+    }
+}
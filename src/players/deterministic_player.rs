@@ -1,6 +1,7 @@
 use crate::core::board::Board;
 use crate::core::card::Card;
 use crate::core::deck::Deck;
+use crate::core::moves::Move;
 use crate::core::player::Player;
 use crate::core::square::Square;
 use crate::core::team::Team;
@@ -8,30 +9,30 @@ use crate::core::team::Team;
 pub struct DeterministicPlayer {}
 
 impl Player for DeterministicPlayer {
-    fn play(&self, team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> (u8, Square) {
-        let (index, card) = hand.iter()
-            .enumerate()
-            .find(|(_, card)| board.can_be_played(card, team))
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> Move {
+        let card = hand.iter()
+            .find(|card| board.can_be_played(card, team))
             .unwrap();
 
-        let square = if card.is_one_eyed_jack() {
-            Square::playable_squares()
+        if card.is_one_eyed_jack() {
+            let square = Square::playable_squares()
                 .find(|square| {
                     board.chip_at(square).map_or(false, |t| t != *team) && !board.in_sequence(square)
                 })
-                .unwrap()
+                .unwrap();
+            Move::Remove { square }
         } else if card.is_two_eyed_jack() {
-            Square::playable_squares()
+            let square = Square::playable_squares()
                 .find(|square| board.chip_at(square).is_none())
-                .unwrap()
+                .unwrap();
+            Move::WildPlace { square }
         } else {
-            *board.squares_for_card(card)
+            let square = *board.squares_for_card(card)
                 .unwrap()
                 .iter()
                 .find(|square| board.chip_at(square).is_none())
-                .unwrap()
-        };
-
-        (index as u8, square)
+                .unwrap();
+            Move::Place { card: *card, square }
+        }
     }
 }
@@ -0,0 +1,362 @@
+use std::cmp::{Ordering, Reverse};
+
+use crate::core::belief_tracker::BeliefTracker;
+use crate::core::board::{Board, SEQUENCE_LENGTH};
+use crate::core::card::Card;
+use crate::core::card_tracker::CardTracker;
+use crate::core::deck::Deck;
+use crate::core::evaluator::Evaluator;
+use crate::core::game::{Game, GameConfig};
+use crate::core::grid_traversal::traverse;
+use crate::core::moves::{apply_move, legal_moves, Move};
+use crate::core::player::Player;
+use crate::core::team::Team;
+use crate::players::square_evaluation_player::SquareEvaluationPlayer;
+
+// score of a won position, well above any static evaluation
+const WIN_SCORE: f32 = 1_000_000.0;
+
+// deterministic search player which alternates between its own moves (max nodes) and chance nodes
+// over the cards that are unseen from its perspective:
+// - for the opponent's reply, each unseen card type is held by the opponent (the player in the next
+//   seat) with the probability given by tracking beliefs about their hand over the game (or, when
+//   not playing in a game, of it being among a hand of the same size as ours); the opponent is
+//   assumed to play its best
+//   available reply (according to the static evaluation), so the reply with the card they prefer
+//   most is weighted by the probability of them holding it, the next by the probability of them
+//   holding it but not the first, and so on
+// - before our next move, each unseen card type is drawn with probability proportional to its
+//   number of unseen copies
+// leaves are evaluated by a static evaluator; to keep the search tractable:
+// - only the best beam_width of our moves (by their static evaluation) at each max node are
+//   searched further, and only the opponent's beam_width most preferred replies are searched
+//   further (with the rest using their static evaluation)
+// - two-eyed jacks are only considered on the jack_candidates empty squares with the most chips
+//   (of any team) in line with them
+// the opponent is the team which plays next, i.e. the next team after ours in seat order, so in
+// three-team games the third team's turn is not searched (though its chips still count in the
+// static evaluation)
+pub struct ExpectimaxPlayer {
+    // number of plies to search, where a ply is either our move or the opponent's reply; e.g. with
+    // a depth of 3, searches our move, the opponent's reply, and our next move after a draw
+    pub depth: usize,
+    pub beam_width: usize,
+    pub jack_candidates: usize,
+
+    // the number of teams in the game, which determines the opponent and how many sequences win; set
+    // at the start of each game
+    pub num_teams: usize,
+
+    // static evaluation of leaves, without considering the hand (since it is unknown in chance
+    // nodes)
+    pub evaluator: Box<dyn Evaluator>,
+
+    // beliefs about the other players' hands; None until the game starts
+    beliefs: Option<BeliefTracker>,
+}
+
+impl Default for ExpectimaxPlayer {
+    fn default() -> Self {
+        ExpectimaxPlayer {
+            depth: 3,
+            beam_width: 4,
+            jack_candidates: 10,
+            num_teams: 2,
+            evaluator: Box::new(SquareEvaluationPlayer::default()),
+            beliefs: None,
+        }
+    }
+}
+
+// information which is constant over the search
+struct SearchContext {
+    team: Team,
+    opponent: Team,
+    winning_sequences: usize,
+    // each distinct unseen card, with the probability that the opponent holds at least one copy
+    // and the probability that it is our next draw
+    unseen: Vec<(Card, f64, f64)>,
+}
+
+impl Player for ExpectimaxPlayer {
+    fn on_game_start(&mut self, seat: usize, _team: &Team, config: &GameConfig) {
+        self.num_teams = config.num_teams;
+        self.beliefs = Some(BeliefTracker::new(seat, config));
+    }
+
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+        let tracker = CardTracker::new(hand, deck);
+        let unseen = tracker.unseen()
+            .map(|(card, count)| {
+                let held = match &self.beliefs {
+                    Some(beliefs) => beliefs.hold_probability(beliefs.next_seat(), card, &tracker),
+                    None => tracker.draw_probability(card, hand.len()),
+                };
+                let drawn = *count as f64 / tracker.unseen_total() as f64;
+                (*card, held, drawn)
+            })
+            .collect();
+
+        let context = SearchContext {
+            team: *team,
+            opponent: Game::player_team(self.num_teams, *team as usize + 1),
+            winning_sequences: Game::winning_sequences(self.num_teams),
+            unseen,
+        };
+
+        let moves = self.candidate_moves(team, hand, board);
+        self.best_move(&context, board, hand, moves, self.depth)
+            .expect("no legal moves")
+            .0
+    }
+
+    fn on_opponent_move(&mut self, seat: usize, _team: &Team, card: &Card, _play: &Move, _board: &Board) {
+        if let Some(beliefs) = &mut self.beliefs {
+            beliefs.on_card_discarded(seat, card);
+        }
+    }
+
+    fn on_opponent_dead_card(&mut self, seat: usize, _team: &Team, card: &Card) {
+        if let Some(beliefs) = &mut self.beliefs {
+            beliefs.on_card_discarded(seat, card);
+        }
+    }
+
+    fn on_opponent_skip(&mut self, seat: usize, team: &Team, board: &Board) {
+        if let Some(beliefs) = &mut self.beliefs {
+            beliefs.on_skip(seat, team, board);
+        }
+    }
+}
+
+impl ExpectimaxPlayer {
+    // max node: returns the best of the given moves from the given hand and its value, searching
+    // the given number of remaining plies (including this one)
+    fn best_move(
+        &self,
+        context: &SearchContext,
+        board: &Board,
+        hand: &[Card],
+        moves: Vec<Move>,
+        depth: usize,
+    ) -> Option<(Move, f32)> {
+        let mut children: Vec<(Move, Board, f32, bool)> = moves.into_iter()
+            .map(|play| {
+                let mut child = board.clone();
+                let won = self.apply(context, &mut child, context.team, &play);
+                let value = if won {
+                    WIN_SCORE + depth as f32
+                } else {
+                    self.evaluator.evaluate(&child, &context.team, None)
+                };
+                (play, child, value, won)
+            })
+            .collect();
+
+        if depth <= 1 {
+            return children.into_iter()
+                .map(|(play, _, value, _)| (play, value))
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        }
+
+        children.sort_by(|(_, _, a, _), (_, _, b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        children.into_iter()
+            .take(self.beam_width)
+            .map(|(play, child, value, won)| {
+                if won {
+                    (play, value)
+                } else {
+                    let mut remaining_hand = hand.to_vec();
+                    remaining_hand.remove(play.card_index(hand).unwrap());
+                    (play, self.opponent_reply(context, &child, &remaining_hand, depth - 1))
+                }
+            })
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+    }
+
+    // chance node for the opponent's reply (from their unknown hand), followed by our draw and next
+    // move if there are plies remaining; returns the expected value for our team
+    fn opponent_reply(&self, context: &SearchContext, board: &Board, hand: &[Card], depth: usize) -> f32 {
+        // the opponent's preferred reply with each unseen card type, with its probability
+        let mut replies: Vec<(f64, Board, f32, bool)> = context.unseen.iter()
+            .filter_map(|(card, held, _)| {
+                self.candidate_moves(&context.opponent, &[*card], board).into_iter()
+                    .map(|play| {
+                        let mut child = board.clone();
+                        let won = self.apply(context, &mut child, context.opponent, &play);
+                        let value = if won {
+                            -WIN_SCORE - depth as f32
+                        } else {
+                            self.evaluator.evaluate(&child, &context.team, None)
+                        };
+                        (*held, child, value, won)
+                    })
+                    .min_by(|(_, _, a, _), (_, _, b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            })
+            .collect();
+        replies.sort_by(|(_, _, a, _), (_, _, b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let mut expected = 0.0;
+        // probability that the opponent holds none of the cards for the replies considered so far
+        let mut none_held = 1.0;
+        for (index, (held, child, value, won)) in replies.into_iter().enumerate() {
+            let value = if depth > 1 && !won && index < self.beam_width {
+                self.our_draw(context, &child, hand, depth - 1)
+            } else {
+                value
+            };
+
+            expected += none_held * held * value as f64;
+            none_held *= 1.0 - held;
+        }
+
+        // the opponent has no playable card and skips their turn
+        let skip_value = if depth > 1 {
+            self.our_draw(context, board, hand, depth - 1)
+        } else {
+            self.evaluator.evaluate(board, &context.team, None)
+        };
+        expected += none_held * skip_value as f64;
+
+        expected as f32
+    }
+
+    // chance node for our next draw, followed by our best move; returns the expected value
+    fn our_draw(&self, context: &SearchContext, board: &Board, hand: &[Card], depth: usize) -> f32 {
+        let moves = self.candidate_moves(&context.team, hand, board);
+        let best_existing = self.best_move(context, board, hand, moves, depth)
+            .map(|(_, value)| value);
+
+        let mut expected = 0.0;
+        for (card, _, drawn) in &context.unseen {
+            let mut new_hand = hand.to_vec();
+            new_hand.push(*card);
+
+            // only consider moves which need the drawn card, since the others are covered by
+            // best_existing
+            let new_moves = self.candidate_moves(&context.team, &new_hand, board).into_iter()
+                .filter(|play| play.card_index(hand).is_none())
+                .collect();
+            let best_new = self.best_move(context, board, &new_hand, new_moves, depth)
+                .map(|(_, value)| value);
+
+            let value = match (best_existing, best_new) {
+                (Some(existing), Some(new)) => existing.max(new),
+                (Some(value), None) | (None, Some(value)) => value,
+                (None, None) => self.evaluator.evaluate(board, &context.team, None),
+            };
+            expected += drawn * value as f64;
+        }
+
+        expected as f32
+    }
+
+    // returns the legal moves for the given hand, with two-eyed jacks restricted to the
+    // jack_candidates most active squares
+    fn candidate_moves(&self, team: &Team, hand: &[Card], board: &Board) -> Vec<Move> {
+        let (mut jack_moves, mut moves): (Vec<_>, Vec<_>) =
+            legal_moves(team, hand, board).into_iter()
+                .partition(|play| matches!(play, Move::WildPlace { .. }));
+
+        jack_moves.sort_by_cached_key(|play| {
+            let mut chips_in_line = 0;
+            traverse(&play.square(), SEQUENCE_LENGTH - 1, &mut |line_square| {
+                if line_square.is_corner() || board.chip_at(&line_square).is_some() {
+                    chips_in_line += 1;
+                }
+                true
+            });
+            Reverse(chips_in_line)
+        });
+
+        moves.extend(jack_moves.into_iter().take(self.jack_candidates));
+        moves
+    }
+
+    // applies the given move to the board, returning true if it wins the game for the team
+    fn apply(&self, context: &SearchContext, board: &mut Board, team: Team, play: &Move) -> bool {
+        apply_move(board, team, play)
+            .map_or(false, |sequences| sequences >= context.winning_sequences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::rank::Rank;
+    use crate::core::square::Square;
+    use crate::core::suit::Suit;
+    use crate::players::random_player::RandomPlayer;
+
+    use super::*;
+
+    #[test]
+    fn completes_winning_sequence() {
+        let mut board = Board::standard_board();
+        let deck = Deck::default();
+        let mut player = ExpectimaxPlayer { depth: 2, ..Default::default() };
+
+        // one sequence already complete, and four of a second on c4-f4 with g4 (ace of hearts) open
+        for square in ["b1", "c1", "d1", "e1", "f1", "c4", "d4", "e4", "f4"] {
+            board.add_chip(&Square::from_notation(square), Team::One);
+        }
+
+        let hand = vec![
+            Card { rank: Rank::Three, suit: Suit::Diamonds },
+            Card { rank: Rank::Ace, suit: Suit::Hearts },
+        ];
+
+        let play = player.play(&Team::One, &hand, &board, &deck);
+        assert_eq!(play, Move::Place { card: hand[1], square: Square::from_notation("g4") });
+    }
+
+    #[test]
+    fn blocks_the_next_team_in_three_team_games() {
+        let mut board = Board::standard_board();
+        let deck = Deck::default();
+        let mut player = ExpectimaxPlayer { depth: 2, num_teams: 3, ..Default::default() };
+
+        // the next team needs only g4 (ace of hearts) for the single sequence which wins
+        for square in ["c4", "d4", "e4", "f4"] {
+            board.add_chip(&Square::from_notation(square), Team::Three);
+        }
+
+        let hand = vec![
+            Card { rank: Rank::Three, suit: Suit::Diamonds },
+            Card { rank: Rank::Ace, suit: Suit::Hearts },
+        ];
+
+        let play = player.play(&Team::Two, &hand, &board, &deck);
+        assert_eq!(play, Move::Place { card: hand[1], square: Square::from_notation("g4") });
+    }
+
+    #[test]
+    fn expectimax_game_runs_without_panics() {
+        let players: Vec<Box<dyn Player>> = vec![
+            Box::new(ExpectimaxPlayer { depth: 2, beam_width: 2, ..Default::default() }),
+            Box::new(RandomPlayer::default()),
+        ];
+
+        play_turns(Game::new(players, 2));
+    }
+
+    #[test]
+    fn three_team_expectimax_game_runs_without_panics() {
+        let players: Vec<Box<dyn Player>> = vec![
+            Box::new(RandomPlayer::default()),
+            Box::new(ExpectimaxPlayer { depth: 2, beam_width: 2, ..Default::default() }),
+            Box::new(RandomPlayer::default()),
+        ];
+
+        play_turns(Game::new(players, 3));
+    }
+
+    // plays the first turns of the game, since playing a whole game with a search player is slow in
+    // debug builds
+    fn play_turns(mut game: Game) {
+        for _ in 0..10 {
+            if game.run_turn().is_some() {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::Deck;
+use crate::core::evaluator::Evaluator;
+use crate::core::moves::{apply_move, legal_moves, Move};
+use crate::core::player::Player;
+use crate::core::team::Team;
+use crate::evaluators::linear_evaluator::LinearEvaluator;
+use crate::util::params::Parameterized;
+
+// player which plays the move leading to the best evaluation by a LinearEvaluator whose weights were
+// learned by self-play (see the trainer module), without any lookahead; load the weights written by
+// the trainer with LearnedPlayer::from_param_file()
+#[derive(Default)]
+pub struct LearnedPlayer {
+    pub evaluator: LinearEvaluator,
+}
+
+impl LearnedPlayer {
+    // returns the position which the player evaluates after the given move: the board, and its hand
+    // without the card played (i.e. before it draws a new one)
+    pub fn after_move(team: &Team, hand: &[Card], board: &Board, play: &Move) -> (Board, Vec<Card>) {
+        let mut child = board.clone();
+        apply_move(&mut child, *team, play);
+
+        let mut remaining_hand = hand.to_vec();
+        remaining_hand.remove(play.card_index(hand).expect("no card in hand for move"));
+        (child, remaining_hand)
+    }
+}
+
+impl Player for LearnedPlayer {
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> Move {
+        legal_moves(team, hand, board).into_iter()
+            .map(|play| {
+                let (child, remaining_hand) = LearnedPlayer::after_move(team, hand, board, &play);
+                (play, self.evaluator.evaluate(&child, team, Some(&remaining_hand)))
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .expect("no legal moves")
+            .0
+    }
+}
+
+// the parameters of a learned player are the weights of its evaluator
+impl Parameterized for LearnedPlayer {
+    fn param_names() -> &'static [&'static str] {
+        LinearEvaluator::param_names()
+    }
+
+    fn param(&self, name: &str) -> Option<f32> {
+        self.evaluator.param(name)
+    }
+
+    fn set_param(&mut self, name: &str, value: f32) -> Result<(), String> {
+        self.evaluator.set_param(name, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::Game;
+    use crate::players::random_player::RandomPlayer;
+
+    use super::*;
+
+    #[test]
+    fn learned_game_runs_without_panics() {
+        let players: Vec<Box<dyn Player>> = vec![
+            Box::new(LearnedPlayer::default()),
+            Box::new(RandomPlayer::default()),
+        ];
+
+        Game::new(players, 2).run();
+    }
+}
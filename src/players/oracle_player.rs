@@ -0,0 +1,183 @@
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::evaluator::Evaluator;
+use crate::core::game::Game;
+use crate::core::moves::{apply_move, legal_moves, Move};
+use crate::core::privileged::{PrivilegedPlayer, PrivilegedView};
+use crate::core::team::Team;
+use crate::evaluators::window_evaluator::WindowEvaluator;
+
+// score of a won position, well above any static evaluation
+const WIN_SCORE: f32 = 1_000_000.0;
+
+// player with perfect knowledge of the game (the order of the draw pile and all other hands), used
+// as an upper bound on the strength of regular players; since the game is deterministic given this
+// knowledge (until the draw pile is reshuffled), it chooses its move by a minimax search assuming
+// that every other team plays to minimize its score
+pub struct OraclePlayer {
+    // number of plies (individual players' turns, starting with this one) to search; this player's
+    // own move is always searched, so 0 is the same as 1
+    pub depth: usize,
+    // static evaluation of the leaves of the search
+    pub evaluator: Box<dyn Evaluator>,
+}
+
+impl Default for OraclePlayer {
+    fn default() -> Self {
+        OraclePlayer { depth: 2, evaluator: Box::new(WindowEvaluator::default()) }
+    }
+}
+
+// state of the game in the search tree
+#[derive(Clone)]
+struct SearchState {
+    board: Board,
+    hands: Vec<Vec<Card>>,
+    // remaining known draw pile; the next card drawn is the last one
+    draw_pile: Vec<Card>,
+    up_index: usize,
+}
+
+impl PrivilegedPlayer for OraclePlayer {
+    fn play(&self, view: &PrivilegedView) -> Move {
+        let game = view.game();
+        let state = SearchState {
+            board: view.board().clone(),
+            hands: (0..game.num_players()).map(|index| view.hand(index).to_vec()).collect(),
+            draw_pile: view.draw_pile().to_vec(),
+            up_index: view.player_index(),
+        };
+
+        let team = view.team();
+        let moves = legal_moves(&team, &state.hands[state.up_index], &state.board);
+
+        let mut best: Option<(Move, f32)> = None;
+        let mut alpha = f32::NEG_INFINITY;
+        for play in moves {
+            let score = self.search_move(&state, play, game, &team, self.depth, alpha, f32::INFINITY);
+            if best.map_or(true, |(_, best_score)| score > best_score) {
+                best = Some((play, score));
+            }
+            alpha = alpha.max(score);
+        }
+
+        best.expect("no legal moves").0
+    }
+}
+
+impl OraclePlayer {
+    // returns the minimax score for the given team of the given play by the player who is up in
+    // the given state, searching the given remaining number of plies including this one
+    #[allow(clippy::too_many_arguments)]
+    fn search_move(
+        &self,
+        state: &SearchState,
+        play: Move,
+        game: &Game,
+        team: &Team,
+        depth: usize,
+        alpha: f32,
+        beta: f32,
+    ) -> f32 {
+        let mover_team = game.team(state.up_index);
+        let mut child = state.clone();
+
+        let card_index = play.card_index(&child.hands[child.up_index]).unwrap();
+        child.hands[child.up_index].remove(card_index);
+        if let Some(sequences) = apply_move(&mut child.board, mover_team, &play) {
+            if sequences >= Game::winning_sequences(game.num_teams()) {
+                // prefer faster wins and slower losses
+                let score = WIN_SCORE + depth as f32;
+                return if mover_team == *team { score } else { -score };
+            }
+        }
+
+        if let Some(drawn) = child.draw_pile.pop() {
+            child.hands[child.up_index].push(drawn);
+        }
+        child.up_index = (child.up_index + 1) % game.num_players();
+
+        self.search(&child, game, team, depth.saturating_sub(1), alpha, beta)
+    }
+
+    // returns the minimax score for the given team of the given state, searching the given
+    // remaining number of plies
+    fn search(
+        &self,
+        state: &SearchState,
+        game: &Game,
+        team: &Team,
+        depth: usize,
+        mut alpha: f32,
+        mut beta: f32,
+    ) -> f32 {
+        // stop searching once the known draw pile is exhausted, since the reshuffle is random
+        if depth == 0 || state.draw_pile.is_empty() {
+            return self.evaluator.evaluate(&state.board, team, None);
+        }
+
+        let mover_team = game.team(state.up_index);
+        let moves = legal_moves(&mover_team, &state.hands[state.up_index], &state.board);
+        if moves.is_empty() {
+            // the player would skip their turn (ignoring dead card exchanges)
+            let mut child = state.clone();
+            child.up_index = (child.up_index + 1) % game.num_players();
+            return self.search(&child, game, team, depth - 1, alpha, beta);
+        }
+
+        let maximizing = mover_team == *team;
+        let mut best = if maximizing { f32::NEG_INFINITY } else { f32::INFINITY };
+        for play in moves {
+            let score = self.search_move(state, play, game, team, depth, alpha, beta);
+            if maximizing {
+                best = best.max(score);
+                alpha = alpha.max(score);
+            } else {
+                best = best.min(score);
+                beta = beta.min(score);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::player::Player;
+    use crate::players::deterministic_player::DeterministicPlayer;
+
+    use super::*;
+
+    #[test]
+    fn oracle_game_runs_without_panics() {
+        for _ in 0..3 {
+            let players: Vec<Box<dyn Player>> = vec![
+                Box::new(DeterministicPlayer {}),
+                Box::new(DeterministicPlayer {}),
+            ];
+
+            let mut game = Game::new(players, 2);
+            game.set_privileged_player(0, Box::new(OraclePlayer { depth: 1, ..Default::default() }));
+            game.run();
+        }
+    }
+
+    #[test]
+    fn zero_depth_searches_only_its_own_move() {
+        for _ in 0..3 {
+            let players: Vec<Box<dyn Player>> = vec![
+                Box::new(DeterministicPlayer {}),
+                Box::new(DeterministicPlayer {}),
+            ];
+
+            let mut game = Game::new(players, 2);
+            game.set_privileged_player(0, Box::new(OraclePlayer { depth: 0, ..Default::default() }));
+            game.run();
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::Deck;
+use crate::core::game::{GameConfig, GameResult};
+use crate::core::moves::Move;
+use crate::core::player::Player;
+use crate::core::team::Team;
+use crate::protocol::{format_card, format_state, format_team, invalid_data, parse_card, PROTOCOL_VERSION};
+
+// player which delegates its decisions to a bot speaking the text protocol described in
+// protocol.rs, usually a child process (see spawn()) but possibly any pair of streams (see
+// connect())
+// - since Player cannot fail, communication errors and invalid replies from the bot panic
+pub struct ProcessPlayer<R = BufReader<ChildStdout>, W = ChildStdin> where R: BufRead, W: Write {
+    name: String,
+    reader: R,
+    writer: W,
+    // the bot's process, if spawned by this player; it is told to quit and waited on when dropped
+    child: Option<Child>,
+}
+
+impl ProcessPlayer {
+    // launches the given command as a bot and waits for it to be ready
+    pub fn spawn(command: &str, args: &[String]) -> io::Result<ProcessPlayer> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let reader = BufReader::new(child.stdout.take().unwrap());
+        let writer = child.stdin.take().unwrap();
+        let mut player = ProcessPlayer::connect(reader, writer)?;
+        player.child = Some(child);
+        Ok(player)
+    }
+}
+
+impl<R, W> ProcessPlayer<R, W> where R: BufRead, W: Write {
+    // connects to a bot reading messages from the given writer and replying on the given reader,
+    // and waits for it to be ready
+    pub fn connect(reader: R, writer: W) -> io::Result<ProcessPlayer<R, W>> {
+        let mut player = ProcessPlayer { name: String::new(), reader, writer, child: None };
+
+        player.send(&[format!("protocol {PROTOCOL_VERSION}")])?;
+        let reply = player.receive()?;
+        let name = reply.strip_prefix("ready")
+            .ok_or_else(|| invalid_data(format!("expected ready, got: {reply}")))?;
+        player.name = name.trim().to_string();
+
+        Ok(player)
+    }
+
+    // returns the name given by the bot, which may be empty
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send(&mut self, lines: &[String]) -> io::Result<()> {
+        for line in lines {
+            writeln!(self.writer, "{line}")?;
+        }
+        self.writer.flush()
+    }
+
+    // returns the next line from the bot which is not a comment
+    fn receive(&mut self) -> io::Result<String> {
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "bot closed its output"));
+            }
+
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                return Ok(line.to_string());
+            }
+        }
+    }
+
+    // sends the given state followed by the given request, and returns the reply
+    fn request(&mut self, request: &str, hand: &[Card], board: &Board, deck: &Deck) -> io::Result<String> {
+        let mut lines = format_state(board, deck, hand);
+        lines.push(request.to_string());
+        self.send(&lines)?;
+        self.receive()
+    }
+
+    // sends the given event, panicking on failure
+    fn notify(&mut self, event: String) {
+        self.send(&[event]).unwrap_or_else(|error| panic!("bot {:?} failed: {}", self.name, error));
+    }
+
+    fn parse_move(reply: &str) -> Result<Move, String> {
+        let tokens: Vec<&str> = reply.split_whitespace().collect();
+        let ["move", card, square] = tokens[..] else {
+            return Err(format!("expected move, got: {reply}"));
+        };
+
+        Ok(Move::for_card(parse_card(card)?, square.parse()?))
+    }
+}
+
+impl<R, W> Player for ProcessPlayer<R, W> where R: BufRead, W: Write {
+    fn on_game_start(&mut self, seat: usize, team: &Team, config: &GameConfig) {
+        self.notify(format!(
+            "start {} {} {} {} {} {} {}",
+            seat,
+            format_team(team),
+            config.num_players,
+            config.num_teams,
+            config.hand_size,
+            config.winning_sequences,
+            config.max_dead_card_exchanges,
+        ));
+    }
+
+    fn play(&mut self, _team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+        self.request("play", hand, board, deck)
+            .and_then(|reply| Self::parse_move(&reply).map_err(invalid_data))
+            .unwrap_or_else(|error| panic!("bot {:?} failed to play: {}", self.name, error))
+    }
+
+    fn replace_dead_card(&mut self, _team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Option<usize> {
+        let reply = self.request("dead", hand, board, deck)
+            .unwrap_or_else(|error| panic!("bot {:?} failed to replace a dead card: {}", self.name, error));
+
+        let tokens: Vec<&str> = reply.split_whitespace().collect();
+        match tokens[..] {
+            ["keep"] => None,
+            ["exchange", card] => {
+                let card = parse_card(card)
+                    .unwrap_or_else(|error| panic!("bot {:?} sent an invalid card: {}", self.name, error));
+                let index = hand.iter().position(|hand_card| *hand_card == card)
+                    .unwrap_or_else(|| panic!("bot {:?} exchanged {} which is not in its hand", self.name, card));
+                Some(index)
+            }
+            _ => panic!("bot {:?} sent an invalid reply to dead: {}", self.name, reply),
+        }
+    }
+
+    fn on_opponent_move(&mut self, seat: usize, team: &Team, card: &Card, play: &Move, _board: &Board) {
+        self.notify(format!("opponent_move {} {} {} {}", seat, format_team(team), format_card(card), play.square()));
+    }
+
+    fn on_opponent_dead_card(&mut self, seat: usize, team: &Team, card: &Card) {
+        self.notify(format!("opponent_dead {} {} {}", seat, format_team(team), format_card(card)));
+    }
+
+    fn on_opponent_skip(&mut self, seat: usize, team: &Team, _board: &Board) {
+        self.notify(format!("opponent_skip {} {}", seat, format_team(team)));
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.notify(format!("end {} {}", format_team(&result.winner), result.turns));
+    }
+}
+
+impl<R, W> Drop for ProcessPlayer<R, W> where R: BufRead, W: Write {
+    fn drop(&mut self) {
+        // the bot may already have exited, so errors are ignored
+        let _ = self.send(&[String::from("quit")]);
+        if let Some(child) = &mut self.child {
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::net::UnixStream;
+    use std::thread;
+
+    use crate::core::game::Game;
+    use crate::players::random_player::RandomPlayer;
+    use crate::players::square_evaluation_player::SquareEvaluationPlayer;
+    use crate::protocol::serve;
+
+    use super::*;
+
+    #[test]
+    fn plays_games_against_a_connected_bot() {
+        let (engine, bot) = UnixStream::pair().unwrap();
+        let bot_thread = thread::spawn(move || {
+            let mut player = SquareEvaluationPlayer::default();
+            let mut output = bot.try_clone().unwrap();
+            serve(&mut player, "square-evaluation", BufReader::new(bot), &mut output)
+        });
+
+        let player = ProcessPlayer::connect(BufReader::new(engine.try_clone().unwrap()), engine).unwrap();
+        assert_eq!(player.name(), "square-evaluation");
+
+        let players: Vec<Box<dyn Player>> = vec![Box::new(RandomPlayer::default()), Box::new(player)];
+        let mut game = Game::new(players, 2);
+        game.run();
+
+        // dropping the game (and so the player) tells the bot to quit
+        drop(game);
+        bot_thread.join().unwrap().unwrap();
+    }
+}
@@ -1,39 +1,61 @@
-use rand::thread_rng;
-use rand::seq::IteratorRandom;
+use rand::rngs::StdRng;
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
 
 use crate::core::board::Board;
 use crate::core::card::Card;
 use crate::core::deck::Deck;
+use crate::core::moves::Move;
 use crate::core::player::Player;
 use crate::core::square::Square;
 use crate::core::team::Team;
 
-pub struct RandomPlayer {}
+pub struct RandomPlayer {
+    // the source of every choice, so that a player created with a seed always makes the same choices
+    rng: StdRng,
+}
+
+impl Default for RandomPlayer {
+    fn default() -> Self {
+        RandomPlayer { rng: StdRng::from_entropy() }
+    }
+}
+
+impl RandomPlayer {
+    // creates a player whose choices are all determined by the given seed
+    pub fn with_seed(seed: u64) -> RandomPlayer {
+        RandomPlayer { rng: StdRng::seed_from_u64(seed) }
+    }
+}
 
-fn rand_unoccupied_square(board: &Board) -> Square {
+fn rand_unoccupied_square(board: &Board, rng: &mut impl Rng) -> Square {
     Square::playable_squares()
         .filter(|square| board.chip_at(square).is_none())
-        .choose(&mut thread_rng())
+        .choose(rng)
         .unwrap()
 }
 
 // returns a random square among those owned by teams other than the given excluding_team and which
 // is not in a sequence
 // i.e. squares which have a chip that can be removed by the given team
-pub fn rand_occupied_square_not_in_sequence(board: &Board, excluding_team: &Team) -> Option<Square> {
+pub fn rand_occupied_square_not_in_sequence(
+    board: &Board,
+    excluding_team: &Team,
+    rng: &mut impl Rng,
+) -> Option<Square> {
     Square::playable_squares()
         .filter(|square| {
             board.chip_at(square).map_or(false, |team| &team != excluding_team) &&
                 !board.in_sequence(square)
         })
-        .choose(&mut thread_rng())
+        .choose(rng)
 }
 
 impl Player for RandomPlayer {
     // TODO avoid recursion for invalid cases for performance
-    fn play(&self, team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> (u8, Square) {
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> Move {
         // choose a random card
-        let (card_index, card) = hand.iter().enumerate().choose(&mut thread_rng()).unwrap();
+        let card = hand.iter().choose(&mut self.rng).unwrap();
 
         if card.is_one_eyed_jack() {
             if board.is_empty() {
@@ -41,8 +63,8 @@ impl Player for RandomPlayer {
                 return self.play(team, hand, board, _deck);
             }
 
-            if let Some(square) = rand_occupied_square_not_in_sequence(board, team) {
-                (card_index as u8, square)
+            if let Some(square) = rand_occupied_square_not_in_sequence(board, team, &mut self.rng) {
+                Move::Remove { square }
             } else {
                 // board is not empty but only has chips from this team; try again
                 self.play(team, hand, board, _deck)
@@ -52,15 +74,19 @@ impl Player for RandomPlayer {
                 // attempted to play a two-eyed jack on a full board; try again
                 return self.play(team, hand, board, _deck);
             }
-            (card_index as u8, rand_unoccupied_square(board))
+            Move::WildPlace { square: rand_unoccupied_square(board, &mut self.rng) }
         } else {
-            let squares = board.squares_for_card(card).unwrap();
-            let square_choice = squares
+            // the squares are sorted since the order of a HashSet differs between runs, which would
+            // make the choice differ for the same seed
+            let mut squares: Vec<Square> = board.squares_for_card(card).unwrap()
                 .iter()
                 .filter(|square| board.chip_at(square).is_none())
-                .choose(&mut thread_rng());
+                .copied()
+                .collect();
+            squares.sort_by_key(|square| square.index());
+            let square_choice = squares.choose(&mut self.rng);
             if let Some(square) = square_choice {
-                (card_index as u8, *square)
+                Move::Place { card: *card, square: *square }
             } else {
                 // attempted to play a dead card; try again
                 self.play(team, hand, board, _deck)
@@ -2,28 +2,120 @@ use std::cmp;
 use std::collections::HashSet;
 
 use itertools::Itertools;
+use strum::IntoEnumIterator;
 
-use crate::core::board::Board;
+use crate::core::board::{Board, SEQUENCE_LENGTH};
 use crate::core::card::Card;
+use crate::core::card_tracker::CardTracker;
 use crate::core::deck::Deck;
-use crate::core::grid_traversal::open_runs_for_team;
+use crate::core::evaluator::Evaluator;
+use crate::core::game::GameConfig;
+use crate::core::grid_traversal::{DIRECTIONS, open_runs_for_team};
 use crate::core::simple_player::SimplePlayer;
 use crate::core::square::Square;
 use crate::core::team::Team;
-use crate::players::random_player::rand_occupied_square_not_in_sequence;
+use crate::util::params::Parameterized;
 
+#[derive(Clone)]
 pub struct SquareEvaluationPlayer {
+    // minimum evaluation of a square for which a two-eyed jack will be played on it
     pub two_eyed_jack_cutoff: i32,
+    // minimum threat of an opponent's chip for which a one-eyed jack will be played to remove it
+    // (unless there is no other playable card)
+    pub one_eyed_jack_cutoff: i32,
+
+    // value of each square in an open run through the evaluated square which already counts for
+    // the team (i.e. has its chip or is a corner)
+    pub owned_square_value: f32,
+    // value of each square in an open run which can be claimed with a (non-jack) card in hand
+    pub reachable_square_value: f32,
+    // value of each other empty square in an open run
+    pub other_square_value: f32,
+    // amount by which the value of squares in a run is reduced per square of distance from the
+    // evaluated square, i.e. a square at distance d has its value divided by 1 + (d - 1) * falloff
+    pub distance_falloff: f32,
+
+    // weight of the defensive term, which values blocking opponents' open runs through the square:
+    // for each opponent and direction, the most opponent chips n in any window of SEQUENCE_LENGTH
+    // squares through the square which could still become a sequence adds weight * n^exponent, if
+    // n is at least min_chips (i.e. lone chips are not considered a threat)
+    pub defense_weight: f32,
+    pub defense_exponent: f32,
+    pub defense_min_chips: i32,
+
+    // the number of teams in the game, whose runs are blocked; set at the start of each game
+    pub num_teams: usize,
 }
 
 impl Default for SquareEvaluationPlayer {
     fn default() -> Self {
         SquareEvaluationPlayer {
             two_eyed_jack_cutoff: 100,
+            one_eyed_jack_cutoff: 40,
+            owned_square_value: 10.0,
+            reachable_square_value: 2.5,
+            other_square_value: 0.5,
+            distance_falloff: 0.05,
+            defense_weight: 1.5,
+            defense_exponent: 3.0,
+            defense_min_chips: 2,
+            num_teams: 2,
         }
     }
 }
 
+impl Parameterized for SquareEvaluationPlayer {
+    fn param_names() -> &'static [&'static str] {
+        &[
+            "two_eyed_jack_cutoff",
+            "one_eyed_jack_cutoff",
+            "owned_square_value",
+            "reachable_square_value",
+            "other_square_value",
+            "distance_falloff",
+            "defense_weight",
+            "defense_exponent",
+            "defense_min_chips",
+        ]
+    }
+
+    fn param(&self, name: &str) -> Option<f32> {
+        match name {
+            "two_eyed_jack_cutoff" => Some(self.two_eyed_jack_cutoff as f32),
+            "one_eyed_jack_cutoff" => Some(self.one_eyed_jack_cutoff as f32),
+            "owned_square_value" => Some(self.owned_square_value),
+            "reachable_square_value" => Some(self.reachable_square_value),
+            "other_square_value" => Some(self.other_square_value),
+            "distance_falloff" => Some(self.distance_falloff),
+            "defense_weight" => Some(self.defense_weight),
+            "defense_exponent" => Some(self.defense_exponent),
+            "defense_min_chips" => Some(self.defense_min_chips as f32),
+            _ => None,
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: f32) -> Result<(), String> {
+        match name {
+            "two_eyed_jack_cutoff" => self.two_eyed_jack_cutoff = value.round() as i32,
+            "one_eyed_jack_cutoff" => self.one_eyed_jack_cutoff = value.round() as i32,
+            "owned_square_value" => self.owned_square_value = value,
+            "reachable_square_value" => self.reachable_square_value = value,
+            "other_square_value" => self.other_square_value = value,
+            "distance_falloff" => {
+                if value < 0.0 {
+                    return Err(format!("distance_falloff must be non-negative: {value}"));
+                }
+                self.distance_falloff = value
+            }
+            "defense_weight" => self.defense_weight = value,
+            "defense_exponent" => self.defense_exponent = value,
+            "defense_min_chips" => self.defense_min_chips = value.round() as i32,
+            _ => return Err(format!("unknown parameter: {name}")),
+        }
+        Ok(())
+    }
+}
+
 impl Board {
     fn normally_playable_squares(&self, cards: &[Card]) -> HashSet<Square> {
         cards.iter()
@@ -33,8 +125,42 @@ impl Board {
     }
 }
 
+// value of each completed sequence in the board evaluation, well above that of any set of squares
+const SEQUENCE_VALUE: f32 = 1000.0;
+
+// number of a team's most valuable empty squares which count towards its board evaluation
+const BOARD_EVALUATION_SQUARES: usize = 3;
+
+// evaluates the whole board (rather than a single square) for a team as the difference between its
+// potential and that of its strongest opponent (among the teams with chips on the board); the
+// potential of a team is its completed sequences plus the evaluations of its most valuable empty
+// squares (taking into account the squares reachable with the hand, if given, for the team itself)
+impl Evaluator for SquareEvaluationPlayer {
+    fn evaluate(&self, board: &Board, team: &Team, hand: Option<&[Card]>) -> f32 {
+        let opponent_potential = Team::iter()
+            .filter(|opponent| opponent != team && board.num_chips_for(opponent) > 0)
+            .map(|opponent| self.potential(board, &opponent, &[]))
+            .fold(0.0, f32::max);
+
+        self.potential(board, team, hand.unwrap_or(&[])) - opponent_potential
+    }
+}
+
 impl SquareEvaluationPlayer {
-    fn evaluate_empty_square(square: &Square, team: &Team, hand: &[Card], board: &Board) -> i32 {
+    fn potential(&self, board: &Board, team: &Team, hand: &[Card]) -> f32 {
+        let mut evaluations: Vec<i32> = Square::playable_squares()
+            .filter(|square| board.chip_at(square).is_none())
+            .map(|square| self.evaluate_empty_square(&square, team, hand, board))
+            .collect();
+        evaluations.sort_unstable_by(|a, b| b.cmp(a));
+
+        let sequences = board.sequences_for(team).count() as f32;
+        let squares: i32 = evaluations.into_iter().take(BOARD_EVALUATION_SQUARES).sum();
+
+        sequences * SEQUENCE_VALUE + squares as f32
+    }
+
+    fn evaluate_empty_square(&self, square: &Square, team: &Team, hand: &[Card], board: &Board) -> i32 {
         let mut score: i32 = 0;
         let normal_squares: HashSet<Square> = board.normally_playable_squares(hand);
 
@@ -42,15 +168,15 @@ impl SquareEvaluationPlayer {
         for run in open_runs_for_team(board, square, team) {
             for run_square in run {
                 let value: f32 = if board.counts_for(&run_square, team) {
-                    10.0
+                    self.owned_square_value
                 } else if normal_squares.contains(&run_square) {
-                    2.5
+                    self.reachable_square_value
                 } else {
-                    0.5
+                    self.other_square_value
                 };
 
                 let dist = SquareEvaluationPlayer::sequence_distance(square, &run_square).unwrap() as f32;
-                let dist_multiplier = (dist - 1.0) * 0.05;
+                let dist_multiplier = (dist - 1.0) * self.distance_falloff;
                 score += (value / (1.0 + dist_multiplier)) as i32;
             }
         }
@@ -58,6 +184,47 @@ impl SquareEvaluationPlayer {
         score
     }
 
+    // evaluates how much placing a chip on the given empty square would block the open runs of
+    // teams other than the given team
+    fn evaluate_blocking(&self, square: &Square, team: &Team, board: &Board) -> i32 {
+        let length = SEQUENCE_LENGTH as i8;
+        let mut score: f32 = 0.0;
+
+        for opponent in Team::iter().take(self.num_teams).filter(|opponent| opponent != team) {
+            for (row_delta, col_delta) in DIRECTIONS {
+                // for each square along the direction within a sequence length of the square:
+                // Some(1) if it counts for the opponent, Some(0) if it is empty (or the square
+                // itself), and None if it is off the board or has a chip from another team
+                let line: Vec<Option<i32>> = (1 - length..length)
+                    .map(|offset| {
+                        let line_square = square.plus(offset * row_delta, offset * col_delta);
+                        if offset == 0 {
+                            Some(0)
+                        } else if !line_square.is_valid() {
+                            None
+                        } else if board.counts_for(&line_square, &opponent) {
+                            Some(1)
+                        } else if board.chip_at(&line_square).is_some() {
+                            None
+                        } else {
+                            Some(0)
+                        }
+                    })
+                    .collect();
+
+                let most_chips = line.windows(SEQUENCE_LENGTH as usize)
+                    .filter_map(|window| window.iter().copied().sum::<Option<i32>>())
+                    .max();
+
+                if let Some(chips) = most_chips.filter(|chips| *chips >= self.defense_min_chips) {
+                    score += self.defense_weight * (chips as f32).powf(self.defense_exponent);
+                }
+            }
+        }
+
+        score as i32
+    }
+
     // TODO extract and test
     fn sequence_distance(square1: &Square, square2: &Square) -> Option<u8> {
         let row_diff = square1.row.abs_diff(square2.row);
@@ -71,13 +238,63 @@ impl SquareEvaluationPlayer {
 }
 
 impl SimplePlayer for SquareEvaluationPlayer {
-    // TODO play one-eyed jacks if evaluation of a square for another team is above a threshold
+    fn on_game_start(&mut self, config: &GameConfig) {
+        self.num_teams = config.num_teams;
+    }
+
     fn play_square(&self, team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> Square {
-        // if there are no open squares, return a random square for a one-eyed jack to remove
-        if board.is_full() {
-            return rand_occupied_square_not_in_sequence(board, team).unwrap();
+        let placement = if board.is_full() { None } else { self.best_placement(team, hand, board) };
+
+        let removal = if hand.iter().any(|card| card.is_one_eyed_jack()) {
+            self.best_removal(team, board)
+        } else {
+            None
+        };
+
+        match (placement, removal) {
+            (Some((square, evaluation)), Some((removal_square, threat))) => {
+                // only spend the one-eyed jack if the threat is significant and removing it is
+                // worth more than the best placement
+                if threat >= self.one_eyed_jack_cutoff && threat > evaluation {
+                    removal_square
+                } else {
+                    square
+                }
+            }
+            (Some((square, _)), None) => square,
+            (None, Some((square, _))) => square,
+            (None, None) => panic!("no playable cards in hand"),
+        }
+    }
+
+    fn replace_dead_card(&self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Option<usize> {
+        // if no unseen card could be played, the replacement would be dead as well; keep the dead
+        // card rather than reveal anything, unless every card in the hand is dead (in which case
+        // keeping it would skip the turn, and the discards will eventually be reshuffled)
+        let tracker = CardTracker::new(hand, deck);
+        let can_play = hand.iter().any(|card| board.can_be_played(card, team));
+        if can_play && !tracker.unseen().any(|(card, _)| board.can_be_played(card, team)) {
+            return None;
         }
 
+        // exchange the dead card which is least likely to become playable again, i.e. the one with
+        // the fewest squares whose chips could be removed by a one-eyed jack
+        hand.iter()
+            .enumerate()
+            .filter(|(_, card)| board.is_dead(card))
+            .min_by_key(|(_, card)| {
+                board.squares_for_card(card).unwrap().iter()
+                    .filter(|square| !board.in_sequence(square))
+                    .count()
+            })
+            .map(|(index, _)| index)
+    }
+}
+
+impl SquareEvaluationPlayer {
+    // returns the best empty square on which to place a chip with a card in the given hand, and its
+    // evaluation; None if no chip can be placed (i.e. the only playable cards are one-eyed jacks)
+    fn best_placement(&self, team: &Team, hand: &[Card], board: &Board) -> Option<(Square, i32)> {
         let normal_squares: HashSet<Square> = board.normally_playable_squares(hand);
 
         let two_eyed_jack_index: Option<usize> = hand.iter()
@@ -93,60 +310,75 @@ impl SimplePlayer for SquareEvaluationPlayer {
         let square_evaluations: Vec<(Square, i32)> = playable_squares
             .filter(|square| board.chip_at(square).is_none())
             .map(|square| {
-                (square, SquareEvaluationPlayer::evaluate_empty_square(&square, team, hand, board))
+                let evaluation = self.evaluate_empty_square(&square, team, hand, board) +
+                    self.evaluate_blocking(&square, team, board);
+                (square, evaluation)
             })
             .collect();
 
         // set of squares with tied maximum evaluation
-        let best_squares: HashSet<Square> = HashSet::from_iter(
-            square_evaluations.iter()
-                .filter(|(square, evaluation)| {
-                    *evaluation >= self.two_eyed_jack_cutoff || normal_squares.contains(square)
-                })
-                .max_set_by_key(|(_, evaluation)| *evaluation)
-                .into_iter()
-                .map(|(square, _)| *square)
-        );
+        let best_squares: Vec<(Square, i32)> = square_evaluations.iter()
+            .filter(|(square, evaluation)| {
+                *evaluation >= self.two_eyed_jack_cutoff || normal_squares.contains(square)
+            })
+            .max_set_by_key(|(_, evaluation)| *evaluation)
+            .into_iter()
+            .copied()
+            .collect();
 
         // if we could not find any squares to evaluate, all our normal cards are dead. then either:
         // 1. we have a two-eyed jack, but there are no squares with an evaluation above the
         //    threshold
-        // 2. we have no two-eyed jack, and must play a one-eyed jack
+        // 2. we have no two-eyed jack, and can only play a one-eyed jack
         if best_squares.is_empty() {
-            return if two_eyed_jack_index.is_some() {
-                // (1) choose the bets square for the two-eyed jack
-                square_evaluations.into_iter()
-                    .max_by_key(|(_, evaluation)| *evaluation)
-                    .map(|(square, _)| square)
-                    .unwrap()
-            } else {
-                // (2) choose a random square for the one-eyed jack
-                rand_occupied_square_not_in_sequence(board, team).unwrap()
-            };
+            // (1) choose the best square for the two-eyed jack; for (2) there are no evaluations
+            return square_evaluations.into_iter().max_by_key(|(_, evaluation)| *evaluation);
         }
 
-        if let Some(square) = best_squares.iter().find(|square| normal_squares.contains(square)) {
+        if let Some(best) = best_squares.iter().find(|(square, _)| normal_squares.contains(square)) {
             // if any of the best squares is playable by a normal card, play it
-            *square
+            Some(*best)
         } else {
             // otherwise, pick an arbitrary best square and play it (with a two-eyed jack)
-            best_squares.into_iter().next().unwrap()
+            best_squares.into_iter().next()
         }
     }
+
+    // returns the most threatening chip from another team which can be removed with a one-eyed
+    // jack, and its threat; None if there are no such chips
+    fn best_removal(&self, team: &Team, board: &Board) -> Option<(Square, i32)> {
+        Square::playable_squares()
+            .filter(|square| !board.in_sequence(square))
+            .filter_map(|square| {
+                board.chip_at(&square)
+                    .filter(|owner| owner != team)
+                    .map(|owner| (square, self.evaluate_threat(&square, &owner, board)))
+            })
+            .max_by_key(|(_, threat)| *threat)
+    }
+
+    // evaluates how much the chip on the given square contributes to its owner's runs, i.e. how
+    // much removing it would set them back; the owner's hand is unknown so no squares in their runs
+    // are considered reachable
+    fn evaluate_threat(&self, square: &Square, owner: &Team, board: &Board) -> i32 {
+        self.evaluate_empty_square(square, owner, &[], board)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::core::game::Game;
     use crate::core::player::Player;
     use crate::core::rank::Rank;
     use crate::core::suit::Suit;
+    use crate::players::random_player::RandomPlayer;
     use super::*;
 
     #[test]
     fn plays_card_near_existing_chip() {
         let mut board = Board::standard_board();
         let deck = Deck::default();
-        let player = SquareEvaluationPlayer::default();
+        let mut player = SquareEvaluationPlayer::default();
 
         board.add_chip(&Square::from_notation("e0"), Team::One);
         // 3 and 4 of clubs are never in line with a corner square
@@ -155,7 +387,7 @@ mod tests {
             Card { rank: Rank::Four, suit: Suit::Clubs },
         ];
 
-        let (index, square) = player.play(&Team::One, &hand, &board, &deck);
+        let (index, square) = player.play(&Team::One, &hand, &board, &deck).to_index(&hand).unwrap();
         assert_eq!(index, 0);
         assert_eq!(square, Square::from_notation("d1"));
     }
@@ -164,7 +396,7 @@ mod tests {
     fn plays_card_near_corner() {
         let board = Board::standard_board();
         let deck = Deck::default();
-        let player = SquareEvaluationPlayer::default();
+        let mut player = SquareEvaluationPlayer::default();
 
         // 3 and 4 of clubs are never in line with a corner square
         let hand = vec![
@@ -173,7 +405,7 @@ mod tests {
             Card { rank: Rank::Three, suit: Suit::Diamonds },
         ];
 
-        let (index, square) = player.play(&Team::One, &hand, &board, &deck);
+        let (index, square) = player.play(&Team::One, &hand, &board, &deck).to_index(&hand).unwrap();
         assert_eq!(index, 2);
         assert_eq!(square, Square::from_notation("j6"));
     }
@@ -182,7 +414,7 @@ mod tests {
     fn plays_card_with_more_nearby_chips() {
         let mut board = Board::standard_board();
         let deck = Deck::default();
-        let player = SquareEvaluationPlayer::default();
+        let mut player = SquareEvaluationPlayer::default();
 
         // run near the 3 of clubs on d1
         board.add_chip(&Square::from_notation("d3"), Team::One);
@@ -197,7 +429,7 @@ mod tests {
             Card { rank: Rank::Four, suit: Suit::Clubs },
         ];
 
-        let (index, square) = player.play(&Team::One, &hand, &board, &deck);
+        let (index, square) = player.play(&Team::One, &hand, &board, &deck).to_index(&hand).unwrap();
         assert_eq!(index, 0);
         assert_eq!(square, Square::from_notation("d1"));
     }
@@ -206,7 +438,7 @@ mod tests {
     fn does_not_play_card_near_blocked_run() {
         let mut board = Board::standard_board();
         let deck = Deck::default();
-        let player = SquareEvaluationPlayer::default();
+        let mut player = SquareEvaluationPlayer::default();
 
         // run up to the 3 of clubs on d1 is blocked
         board.add_chip(&Square::from_notation("d2"), Team::Two);
@@ -225,8 +457,152 @@ mod tests {
             Card { rank: Rank::Four, suit: Suit::Clubs },
         ];
 
-        let (index, square) = player.play(&Team::One, &hand, &board, &deck);
+        let (index, square) = player.play(&Team::One, &hand, &board, &deck).to_index(&hand).unwrap();
         assert_eq!(index, 1);
         assert_eq!(square, Square::from_notation("c1"));
     }
+
+    #[test]
+    fn construct_from_param_string() {
+        let player = SquareEvaluationPlayer::from_param_string(
+            "two_eyed_jack_cutoff=50,owned_square_value=8.5",
+        ).unwrap();
+
+        assert_eq!(player.two_eyed_jack_cutoff, 50);
+        assert_eq!(player.owned_square_value, 8.5);
+        assert_eq!(player.reachable_square_value, 2.5);
+
+        let round_trip = SquareEvaluationPlayer::from_param_string(&player.to_param_string()).unwrap();
+        assert_eq!(round_trip.params(), player.params());
+
+        assert!(SquareEvaluationPlayer::from_param_string("unknown=1").is_err());
+    }
+
+    #[test]
+    fn removes_threatening_chip_with_one_eyed_jack() {
+        let mut board = Board::standard_board();
+        let deck = Deck::default();
+        let mut player = SquareEvaluationPlayer::default();
+
+        // opponent has four in a row on c4-f4
+        board.add_chip(&Square::from_notation("c4"), Team::Two);
+        board.add_chip(&Square::from_notation("d4"), Team::Two);
+        board.add_chip(&Square::from_notation("e4"), Team::Two);
+        board.add_chip(&Square::from_notation("f4"), Team::Two);
+
+        // isolated chip which is not a threat
+        board.add_chip(&Square::from_notation("i1"), Team::Two);
+
+        let hand = vec![
+            Card { rank: Rank::Three, suit: Suit::Clubs },
+            Card { rank: Rank::Jack, suit: Suit::Spades },
+        ];
+
+        let (index, square) = player.play(&Team::One, &hand, &board, &deck).to_index(&hand).unwrap();
+        assert_eq!(index, 1);
+        assert!(["c4", "d4", "e4", "f4"].contains(&format!("{square}").as_str()), "removed {square}");
+    }
+
+    #[test]
+    fn does_not_remove_isolated_chip_with_one_eyed_jack() {
+        let mut board = Board::standard_board();
+        let deck = Deck::default();
+        let mut player = SquareEvaluationPlayer::default();
+
+        board.add_chip(&Square::from_notation("i1"), Team::Two);
+
+        let hand = vec![
+            Card { rank: Rank::Three, suit: Suit::Clubs },
+            Card { rank: Rank::Jack, suit: Suit::Spades },
+        ];
+
+        let (index, _) = player.play(&Team::One, &hand, &board, &deck).to_index(&hand).unwrap();
+        assert_eq!(index, 0);
+    }
+
+    #[test]
+    fn blocks_opponent_with_four_in_a_row() {
+        let mut board = Board::standard_board();
+        let deck = Deck::default();
+        let mut player = SquareEvaluationPlayer::default();
+
+        // opponent has four in a row on c3-f3, open only on g3 (2 of clubs) since b3 is blocked
+        board.add_chip(&Square::from_notation("b3"), Team::One);
+        board.add_chip(&Square::from_notation("c3"), Team::Two);
+        board.add_chip(&Square::from_notation("d3"), Team::Two);
+        board.add_chip(&Square::from_notation("e3"), Team::Two);
+        board.add_chip(&Square::from_notation("f3"), Team::Two);
+
+        // own run near the 3 of diamonds on d2
+        board.add_chip(&Square::from_notation("e2"), Team::One);
+        board.add_chip(&Square::from_notation("f2"), Team::One);
+
+        let hand = vec![
+            Card { rank: Rank::Three, suit: Suit::Diamonds },
+            Card { rank: Rank::Two, suit: Suit::Clubs },
+        ];
+
+        let (index, square) = player.play(&Team::One, &hand, &board, &deck).to_index(&hand).unwrap();
+        assert_eq!(index, 1);
+        assert_eq!(square, Square::from_notation("g3"));
+    }
+
+    #[test]
+    fn blocks_only_teams_in_the_game() {
+        let mut board = Board::standard_board();
+        for square in ["c4", "d4", "e4"] {
+            board.add_chip(&Square::from_notation(square), Team::Three);
+        }
+
+        let f4 = Square::from_notation("f4");
+        assert_eq!(SquareEvaluationPlayer::default().evaluate_blocking(&f4, &Team::One, &board), 0);
+
+        let mut player = SquareEvaluationPlayer::default();
+        let players: Vec<Box<dyn Player>> = (0..3)
+            .map(|_| Box::new(RandomPlayer::default()) as Box<dyn Player>)
+            .collect();
+        Player::on_game_start(&mut player, 0, &Team::One, &Game::new(players, 3).config());
+        assert!(player.evaluate_blocking(&f4, &Team::One, &board) > 0);
+    }
+
+    #[test]
+    fn replaces_dead_card_which_cannot_become_playable() {
+        let mut board = Board::standard_board();
+        let deck = Deck::default();
+        let player = SquareEvaluationPlayer::default();
+
+        // both 3 of clubs squares are taken, but d1 could still be freed by a one-eyed jack
+        board.add_chip(&Square::from_notation("d1"), Team::Two);
+        board.add_chip(&Square::from_notation("f3"), Team::Two);
+
+        // both 2 of clubs squares are taken and in sequences, so it can never become playable
+        for square in ["g1", "g2", "g3", "g4", "g5", "e1", "e2", "e3", "e4", "e5"] {
+            board.add_chip(&Square::from_notation(square), Team::One);
+        }
+        assert!(board.in_sequence(&Square::from_notation("g3")));
+        assert!(board.in_sequence(&Square::from_notation("e1")));
+
+        let hand = vec![
+            Card { rank: Rank::Three, suit: Suit::Clubs },
+            Card { rank: Rank::Four, suit: Suit::Clubs },
+            Card { rank: Rank::Two, suit: Suit::Clubs },
+        ];
+
+        let choice = SimplePlayer::replace_dead_card(&player, &Team::One, &hand, &board, &deck);
+        assert_eq!(choice, Some(2));
+    }
+
+    #[test]
+    fn board_evaluation_prefers_own_runs() {
+        let mut board = Board::standard_board();
+        let player = SquareEvaluationPlayer::default();
+
+        board.add_chip(&Square::from_notation("c4"), Team::One);
+        board.add_chip(&Square::from_notation("d4"), Team::One);
+        board.add_chip(&Square::from_notation("e4"), Team::One);
+        board.add_chip(&Square::from_notation("i1"), Team::Two);
+
+        assert!(player.evaluate(&board, &Team::One, None) > 0.0);
+        assert!(player.evaluate(&board, &Team::Two, None) < 0.0);
+    }
 }
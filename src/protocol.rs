@@ -0,0 +1,313 @@
+use std::collections::HashSet;
+use std::io;
+use std::io::{BufRead, Write};
+
+use crate::core::board::{Board, BOARD_SIZE};
+use crate::core::card::Card;
+use crate::core::deck::{Deck, DiscardReason};
+use crate::core::game::{GameConfig, GameResult};
+use crate::core::moves::{apply_move, Move};
+use crate::core::player::Player;
+use crate::core::rank::Rank;
+use crate::core::square::Square;
+use crate::core::suit::Suit;
+use crate::core::team::Team;
+
+// line-based text protocol between the engine and a bot running in a separate process (see
+// ProcessPlayer), in the spirit of UCI for chess; every message is a single line of
+// space-separated tokens where:
+// - cards are a rank (2-9, T, J, Q, K, A) followed by a suit (s, h, d, c), e.g. Ts or Jd
+// - squares are a column (a-j) followed by a row (0-9), e.g. e4
+// - teams are numbered from 1
+//
+// messages from the engine to the bot:
+// - protocol <version>: always the first message; the bot replies "ready [name]"
+// - start <seat> <team> <players> <teams> <hand size> <winning sequences> <max dead exchanges>:
+//   at the start of each game
+// - board <chips>: the chips on the board as 100 characters in row-major order, either '.' for no
+//   chip or the number of the team with a chip on the square
+// - sequence <team> <square>...: a completed sequence, sent after each board message (once for
+//   each sequence)
+// - deck <draw pile size> <reshuffles> <discard pile size> <discard>...: the public state of the
+//   deck, where each discard (since the start of the game, in order) is a card followed by :p if it
+//   was played or :d if it was exchanged as dead, e.g. Ts:p
+// - hand <card>...: the bot's hand
+// - play: the bot replies "move <card> <square>" to play the card on the square; the kind of move
+//   is determined by the card (two-eyed jacks place a chip anywhere, one-eyed jacks remove one)
+// - dead: the bot may exchange a dead card, replying "exchange <card>" or "keep"
+// - opponent_move <seat> <team> <card> <square>: another player made a move
+// - opponent_dead <seat> <team> <card>: another player exchanged a dead card
+// - opponent_skip <seat> <team>: another player skipped their turn
+// - end <winning team> <turns>: the game is over
+// - quit: the bot should exit
+// the board, sequence, deck and hand messages are sent before each play and dead message; lines
+// from the bot starting with # are ignored, e.g. for debugging output
+pub const PROTOCOL_VERSION: u32 = 1;
+
+pub fn format_card(card: &Card) -> String {
+    let suit = match card.suit {
+        Suit::Spades => 's',
+        Suit::Hearts => 'h',
+        Suit::Diamonds => 'd',
+        Suit::Clubs => 'c',
+    };
+    format!("{}{}", card.rank, suit)
+}
+
+// parses a card as formatted by format_card() (the Display form of the card is also accepted)
+pub fn parse_card(token: &str) -> Result<Card, String> {
+    token.parse()
+}
+
+pub fn format_team(team: &Team) -> String {
+    let number = match team {
+        Team::One => 1,
+        Team::Two => 2,
+        Team::Three => 3,
+    };
+    number.to_string()
+}
+
+pub fn parse_team(token: &str) -> Result<Team, String> {
+    match token {
+        "1" => Ok(Team::One),
+        "2" => Ok(Team::Two),
+        "3" => Ok(Team::Three),
+        _ => Err(format!("invalid team: {token}")),
+    }
+}
+
+// returns the lines describing the given state, sent before each play and dead message
+pub fn format_state(board: &Board, deck: &Deck, hand: &[Card]) -> Vec<String> {
+    let chips: String = Square::all_squares()
+        .map(|square| board.chip_at(&square).map_or(String::from("."), |team| format_team(&team)))
+        .collect();
+
+    let mut lines = vec![format!("board {chips}")];
+    for (team, squares) in board.sequences() {
+        let mut squares: Vec<String> = squares.iter().map(|square| square.to_string()).collect();
+        squares.sort();
+        lines.push(format!("sequence {} {}", format_team(team), squares.join(" ")));
+    }
+
+    let discards: Vec<String> = deck.history().iter()
+        .map(|(card, reason)| {
+            let reason = match reason {
+                DiscardReason::Played => 'p',
+                DiscardReason::Dead => 'd',
+            };
+            format!("{}:{}", format_card(card), reason)
+        })
+        .collect();
+    let deck_line = format!(
+        "deck {} {} {} {}",
+        deck.draw_pile_size(), deck.reshuffles(), deck.discard_pile().len(), discards.join(" "),
+    );
+    lines.push(deck_line.trim_end().to_string());
+
+    let hand: Vec<String> = hand.iter().map(format_card).collect();
+    lines.push(format!("hand {}", hand.join(" ")).trim_end().to_string());
+
+    lines
+}
+
+// the state of the game as received by a bot
+struct BotState {
+    team: Option<Team>,
+    board: Board,
+    chips: [[Option<Team>; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+    sequences: Vec<(Team, HashSet<Square>)>,
+    deck: Deck,
+    hand: Vec<Card>,
+}
+
+// runs the bot side of the protocol for the given player, reading messages from the given input
+// and writing replies to the given output until a quit message or the end of the input
+pub fn serve<P, R, W>(player: &mut P, name: &str, input: R, output: &mut W) -> io::Result<()>
+    where P: Player + ?Sized, R: BufRead, W: Write {
+    let cards = *Board::standard_board().cards();
+    let mut state = BotState {
+        team: None,
+        board: Board::standard_board(),
+        chips: [[None; BOARD_SIZE as usize]; BOARD_SIZE as usize],
+        sequences: vec![],
+        deck: Deck::from_public_state(0, 0, vec![], 0).map_err(invalid_data)?,
+        hand: vec![],
+    };
+
+    for line in input.lines() {
+        let line = line?;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = tokens.split_first() else { continue };
+
+        match command {
+            "protocol" => writeln!(output, "ready {name}")?,
+            "start" => {
+                let numbers = parse_numbers(args, 7)?;
+                let config = GameConfig {
+                    num_players: numbers[2],
+                    num_teams: numbers[3],
+                    hand_size: numbers[4],
+                    winning_sequences: numbers[5],
+                    max_dead_card_exchanges: numbers[6],
+                };
+                let team = parse_team(args[1]).map_err(invalid_data)?;
+                player.on_game_start(numbers[0], &team, &config);
+                state.team = Some(team);
+            }
+            "board" => {
+                let chips = args.first().copied().unwrap_or_default();
+                if chips.chars().count() != Square::all_squares().count() {
+                    return Err(invalid_data(format!("invalid board: {line}")));
+                }
+                for (square, chip) in Square::all_squares().zip(chips.chars()) {
+                    state.chips[square.row as usize][square.col as usize] = match chip {
+                        '.' => None,
+                        _ => Some(parse_team(&chip.to_string()).map_err(invalid_data)?),
+                    };
+                }
+                state.sequences.clear();
+                state.board = Board::restore(cards, state.chips, vec![]).map_err(invalid_data)?;
+            }
+            "sequence" => {
+                let (team, squares) = args.split_first()
+                    .ok_or_else(|| invalid_data(format!("invalid sequence: {line}")))?;
+                let team = parse_team(team).map_err(invalid_data)?;
+                let squares = squares.iter()
+                    .map(|square| square.parse())
+                    .collect::<Result<HashSet<Square>, String>>()
+                    .map_err(invalid_data)?;
+                state.sequences.push((team, squares));
+                state.board = Board::restore(cards, state.chips, state.sequences.clone())
+                    .map_err(invalid_data)?;
+            }
+            "deck" => {
+                let numbers = parse_numbers(args, 3)?;
+                let history = args[3..].iter()
+                    .map(|discard| {
+                        let (card, reason) = discard.split_once(':')
+                            .ok_or_else(|| format!("invalid discard: {discard}"))?;
+                        let reason = match reason {
+                            "p" => DiscardReason::Played,
+                            "d" => DiscardReason::Dead,
+                            _ => return Err(format!("invalid discard reason: {discard}")),
+                        };
+                        Ok((parse_card(card)?, reason))
+                    })
+                    .collect::<Result<Vec<_>, String>>()
+                    .map_err(invalid_data)?;
+                state.deck = Deck::from_public_state(numbers[0], numbers[1], history, numbers[2])
+                    .map_err(invalid_data)?;
+            }
+            "hand" => {
+                state.hand = args.iter()
+                    .map(|card| parse_card(card))
+                    .collect::<Result<Vec<Card>, String>>()
+                    .map_err(invalid_data)?;
+            }
+            "play" => {
+                let team = own_team(&state)?;
+                let play = player.play(&team, &state.hand, &state.board, &state.deck);
+                let card = play.card_index(&state.hand).map_or_else(
+                    // not a legal move; send any card of the right kind so that the engine reports it
+                    || match play {
+                        Move::Place { card, .. } => card,
+                        Move::WildPlace { .. } => Card { rank: Rank::Jack, suit: Suit::Diamonds },
+                        Move::Remove { .. } => Card { rank: Rank::Jack, suit: Suit::Spades },
+                    },
+                    |index| state.hand[index],
+                );
+                writeln!(output, "move {} {}", format_card(&card), play.square())?;
+
+                // keep the board up to date for any events before the next request
+                apply_move(&mut state.board, team, &play);
+            }
+            "dead" => {
+                let team = own_team(&state)?;
+                // an index outside the hand is treated as keeping it, as the game does
+                match player.replace_dead_card(&team, &state.hand, &state.board, &state.deck)
+                    .and_then(|index| state.hand.get(index)) {
+                    Some(card) => writeln!(output, "exchange {}", format_card(card))?,
+                    None => writeln!(output, "keep")?,
+                }
+            }
+            "opponent_move" if args.len() == 4 => {
+                let seat = parse_numbers(args, 1)?[0];
+                let team = parse_team(args[1]).map_err(invalid_data)?;
+                let card = parse_card(args[2]).map_err(invalid_data)?;
+                let play = Move::for_card(card, args[3].parse().map_err(invalid_data)?);
+
+                apply_move(&mut state.board, team, &play);
+                player.on_opponent_move(seat, &team, &card, &play, &state.board);
+            }
+            "opponent_dead" if args.len() == 3 => {
+                let seat = parse_numbers(args, 1)?[0];
+                let team = parse_team(args[1]).map_err(invalid_data)?;
+                player.on_opponent_dead_card(seat, &team, &parse_card(args[2]).map_err(invalid_data)?);
+            }
+            "opponent_skip" if args.len() == 2 => {
+                let seat = parse_numbers(args, 1)?[0];
+                player.on_opponent_skip(seat, &parse_team(args[1]).map_err(invalid_data)?, &state.board);
+            }
+            "end" if args.len() == 2 => {
+                let winner = parse_team(args[0]).map_err(invalid_data)?;
+                let turns = parse_numbers(&args[1..], 1)?[0];
+                player.on_game_end(&GameResult { winner, turns });
+            }
+            "quit" => return Ok(()),
+            _ => return Err(invalid_data(format!("unknown message: {line}"))),
+        }
+
+        output.flush()?;
+    }
+
+    Ok(())
+}
+
+fn own_team(state: &BotState) -> io::Result<Team> {
+    state.team.ok_or_else(|| invalid_data(String::from("request before the start of a game")))
+}
+
+// parses the first count tokens as numbers
+fn parse_numbers(tokens: &[&str], count: usize) -> io::Result<Vec<usize>> {
+    if tokens.len() < count {
+        return Err(invalid_data(format!("expected {} numbers, got {}", count, tokens.len())));
+    }
+
+    tokens[..count].iter()
+        .map(|token| token.parse().map_err(|_| invalid_data(format!("invalid number: {token}"))))
+        .collect()
+}
+
+pub fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::players::deterministic_player::DeterministicPlayer;
+
+    use super::*;
+
+    // always asks to exchange a card which is not in its hand
+    struct OutOfRangeDeadCardPlayer {}
+
+    impl Player for OutOfRangeDeadCardPlayer {
+        fn play(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+            DeterministicPlayer {}.play(team, hand, board, deck)
+        }
+
+        fn replace_dead_card(&mut self, _team: &Team, _hand: &[Card], _board: &Board, _deck: &Deck) -> Option<usize> {
+            Some(99)
+        }
+    }
+
+    #[test]
+    fn out_of_range_dead_card_choices_keep_the_hand() {
+        let input = "protocol 1\nstart 0 1 2 2 7 2 1\nhand 2s 3s\ndead\nquit\n";
+        let mut output = vec![];
+        serve(&mut OutOfRangeDeadCardPlayer {}, "test", input.as_bytes(), &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "ready test\nkeep\n");
+    }
+}
@@ -0,0 +1,456 @@
+// Python bindings, enabled by the "python" feature and built into an extension module named
+// sequence with maturin (see pyproject.toml)
+//
+// players are given as the names of bots (see players::BOT_NAMES) or as Python objects with a
+// play(team, hand, board) method returning a (card, square) pair, and optionally
+// replace_dead_card(team, hand, board) returning the index of a dead card to exchange (or None),
+// on_game_start(seat, team), on_opponent_move(seat, team, card, square) and
+// on_game_end(winner, turns); cards, squares and teams are strings such as "T♠", "e4" and "BLUE"
+//
+// a Python player's move is chosen before the engine runs its turn, so an error in choosing it (or
+// an illegal move) is raised with the game as it was, other than any dead card exchange; the game
+// cannot continue after an error in choosing a dead card, since the card has been kept by then
+
+// the code generated by pyo3 0.22 for functions returning PyResult trips this lint
+#![allow(clippy::useless_conversion)]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::{Deck, DiscardReason};
+use crate::core::game::{Game, GameConfig, GameResult};
+use crate::core::moves::{legal_moves, Move};
+use crate::core::player::{first_dead_card, Player};
+use crate::core::square::Square;
+use crate::core::team::Team;
+use crate::players::{create_bot, create_seeded_bot, BOT_NAMES};
+use crate::render::text::{BoardText, TextOptions};
+
+// the state shared between a game and the Python players in it
+#[derive(Default)]
+struct Shared {
+    // the first error raised by a Python player's callbacks in the engine, which is raised again once
+    // the engine returns (the game cannot be interrupted part way through a turn)
+    error: Option<PyErr>,
+    // set if the error was in choosing a dead card
+    failed: bool,
+    // the move chosen for the Python player who is up
+    play: Option<Move>,
+}
+
+#[pyclass(name = "Game", unsendable)]
+pub struct PyGame {
+    game: Game,
+    result: Option<GameResult>,
+    seed: Option<u64>,
+    // the Python object playing each seat, if it is not a bot
+    python_players: Vec<Option<PyObject>>,
+    shared: Rc<RefCell<Shared>>,
+    failed: bool,
+}
+
+#[pymethods]
+impl PyGame {
+    // creates a game between the given players in the given number of teams, whose deals are
+    // determined by the seed if one is given
+    #[new]
+    #[pyo3(signature = (players, num_teams = 2, seed = None))]
+    fn new(players: Vec<Bound<'_, PyAny>>, num_teams: usize, seed: Option<u64>) -> PyResult<PyGame> {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let game = new_game(create_players(&players, &shared, seed)?, num_teams, seed)?;
+        let python_players = players.iter()
+            .map(|player| if player.is_instance_of::<PyString>() { None } else { Some(player.clone().unbind()) })
+            .collect();
+        Ok(PyGame { game, result: None, seed, python_players, shared, failed: false })
+    }
+
+    // plays the next turn, returning the result if the game is now over
+    fn run_turn(&mut self, py: Python<'_>) -> PyResult<Option<PyGameResult>> {
+        if self.result.is_some() {
+            return Err(PyValueError::new_err("the game is over"));
+        }
+        if self.failed {
+            return Err(PyValueError::new_err("the game cannot continue after an error in choosing a dead card"));
+        }
+
+        let seat = self.game.up_index();
+        if let Some(player) = self.python_players[seat].as_ref().map(|player| player.clone_ref(py)) {
+            self.game.exchange_dead_cards();
+            self.raise_player_error()?;
+
+            let (team, hand, board) = (self.game.team(seat), self.game.hand(seat), self.game.board());
+            if hand.iter().any(|card| board.can_be_played(card, &team)) {
+                let play = choose_move(player.bind(py), &team, hand, board)?;
+                self.shared.borrow_mut().play = Some(play);
+            }
+        }
+
+        self.result = self.game.run_turn();
+        self.raise_player_error()?;
+        Ok(self.result.map(|result| PyGameResult::new(&self.game, result, self.seed)))
+    }
+
+    // plays the game until it is over, returning the result
+    fn run(&mut self, py: Python<'_>) -> PyResult<PyGameResult> {
+        loop {
+            if let Some(result) = self.run_turn(py)? {
+                return Ok(result);
+            }
+        }
+    }
+
+    #[getter]
+    fn board(&self) -> PyBoard {
+        PyBoard { board: self.game.board().clone() }
+    }
+
+    #[getter]
+    fn turns(&self) -> usize {
+        self.game.turn_count()
+    }
+
+    // the seat of the player whose turn is next
+    #[getter]
+    fn up(&self) -> usize {
+        self.game.up_index()
+    }
+
+    #[getter]
+    fn result(&self) -> Option<PyGameResult> {
+        self.result.map(|result| PyGameResult::new(&self.game, result, self.seed))
+    }
+
+    fn team(&self, seat: usize) -> PyResult<String> {
+        self.check_seat(seat)?;
+        Ok(self.game.team(seat).to_string())
+    }
+
+    fn hand(&self, seat: usize) -> PyResult<Vec<String>> {
+        self.check_seat(seat)?;
+        Ok(cards_to_strings(self.game.hand(seat)))
+    }
+}
+
+impl PyGame {
+    fn check_seat(&self, seat: usize) -> PyResult<()> {
+        if seat >= self.game.num_players() {
+            return Err(PyValueError::new_err(format!("no such seat: {seat}")));
+        }
+        Ok(())
+    }
+
+    // raises the error from a Python player's callback in the engine, if there was one
+    fn raise_player_error(&mut self) -> PyResult<()> {
+        let mut shared = self.shared.borrow_mut();
+        self.failed |= shared.failed;
+        shared.error.take().map_or(Ok(()), Err)
+    }
+}
+
+#[pyclass(name = "Board")]
+#[derive(Clone)]
+pub struct PyBoard {
+    board: Board,
+}
+
+#[pymethods]
+impl PyBoard {
+    // an empty standard board
+    #[new]
+    fn new() -> PyBoard {
+        PyBoard { board: Board::standard_board() }
+    }
+
+    // the cards on each square, by row and then column (None for the corners)
+    fn cards(&self) -> Vec<Vec<Option<String>>> {
+        self.board.cards().iter()
+            .map(|row| row.iter().map(|card| card.map(|card| card.to_string())).collect())
+            .collect()
+    }
+
+    fn card_at(&self, square: &str) -> PyResult<Option<String>> {
+        Ok(self.board.card_at(&parse(square)?).map(|card| card.to_string()))
+    }
+
+    fn chip_at(&self, square: &str) -> PyResult<Option<String>> {
+        Ok(self.board.chip_at(&parse(square)?).map(|team| team.to_string()))
+    }
+
+    // the squares of each completed sequence, with the team owning it
+    fn sequences(&self) -> Vec<(String, Vec<String>)> {
+        self.board.sequences().iter()
+            .map(|(team, squares)| {
+                let mut squares: Vec<Square> = squares.iter().copied().collect();
+                squares.sort_by_key(|square| square.index());
+                (team.to_string(), squares.iter().map(|square| square.to_string()).collect())
+            })
+            .collect()
+    }
+
+    fn is_dead(&self, card: &str) -> PyResult<bool> {
+        Ok(self.board.is_dead(&parse(card)?))
+    }
+
+    // the legal (card, square) moves for the given team with the given hand
+    fn legal_moves(&self, team: &str, hand: Vec<String>) -> PyResult<Vec<(String, String)>> {
+        let hand = hand.iter().map(|card| parse(card)).collect::<PyResult<Vec<Card>>>()?;
+        Ok(legal_moves(&parse(team)?, &hand, &self.board).iter()
+            .filter_map(|play| play.card_index(&hand).map(|index| move_tuple(&hand[index], play)))
+            .collect())
+    }
+
+    // the board as plain text (see render::text), with only the chips if compact
+    #[pyo3(signature = (compact = false))]
+    fn to_text(&self, compact: bool) -> String {
+        BoardText::new(&self.board, &TextOptions { compact, ..Default::default() }).to_string()
+    }
+
+    fn __str__(&self) -> String {
+        self.to_text(false)
+    }
+}
+
+// the result of a game, with statistics about how it went
+#[pyclass(name = "GameResult", get_all)]
+#[derive(Clone)]
+pub struct PyGameResult {
+    winner: String,
+    turns: usize,
+    // the seed the game was dealt with, if any
+    seed: Option<u64>,
+    // the number of chips and completed sequences of each team at the end of the game
+    chips: HashMap<String, usize>,
+    sequences: HashMap<String, usize>,
+    // the number of dead cards exchanged during the game
+    dead_cards: usize,
+    // the number of times the discard pile was shuffled into the draw pile
+    reshuffles: usize,
+}
+
+impl PyGameResult {
+    fn new(game: &Game, result: GameResult, seed: Option<u64>) -> PyGameResult {
+        let board = game.board();
+        let teams = game.teams();
+        PyGameResult {
+            winner: result.winner.to_string(),
+            turns: result.turns,
+            seed,
+            chips: teams.iter().map(|team| (team.to_string(), board.num_chips_for(team))).collect(),
+            sequences: teams.iter().map(|team| (team.to_string(), board.sequences_for(team).count())).collect(),
+            dead_cards: game.deck().history().iter().filter(|(_, reason)| *reason == DiscardReason::Dead).count(),
+            reshuffles: game.deck().reshuffles(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyGameResult {
+    fn __repr__(&self) -> String {
+        format!("GameResult(winner={:?}, turns={})", self.winner, self.turns)
+    }
+}
+
+// plays n games between the given players (as for Game), the i-th of them with seed + i if a seed
+// is given, and returns their results; games between bots only are played without holding the GIL
+#[pyfunction]
+#[pyo3(signature = (players, n, seed = None, num_teams = 2))]
+fn simulate(
+    py: Python<'_>,
+    players: Vec<Bound<'_, PyAny>>,
+    n: usize,
+    seed: Option<u64>,
+    num_teams: usize,
+) -> PyResult<Vec<PyGameResult>> {
+    let game_seed = |i: usize| seed.map(|seed| seed.wrapping_add(i as u64));
+
+    let bots: Option<Vec<String>> = players.iter().map(|player| player.extract::<String>().ok()).collect();
+    if let Some(bots) = bots {
+        // checks the bots and number of teams before releasing the GIL
+        new_game(create_players(&players, &Rc::default(), None)?, num_teams, None)?;
+
+        return Ok(py.allow_threads(|| {
+            (0..n)
+                .map(|i| {
+                    let mut seeds = game_seed(i).map(StdRng::seed_from_u64);
+                    let players = bots.iter().map(|bot| named_bot(bot, seeds.as_mut()).unwrap()).collect();
+                    let mut game = new_game(players, num_teams, game_seed(i)).unwrap();
+                    let result = game.run();
+                    PyGameResult::new(&game, result, game_seed(i))
+                })
+                .collect()
+        }));
+    }
+
+    (0..n)
+        .map(|i| {
+            let mut game = PyGame::new(players.clone(), num_teams, game_seed(i))?;
+            game.run(py)
+        })
+        .collect()
+}
+
+#[pymodule]
+fn sequence(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGame>()?;
+    module.add_class::<PyBoard>()?;
+    module.add_class::<PyGameResult>()?;
+    module.add_function(wrap_pyfunction!(simulate, module)?)?;
+    module.add("BOTS", BOT_NAMES.to_vec())?;
+    Ok(())
+}
+
+fn new_game(players: Vec<Box<dyn Player>>, num_teams: usize, seed: Option<u64>) -> PyResult<Game> {
+    if !Game::is_supported(players.len(), num_teams) {
+        let message = format!("unsupported game of {} players in {num_teams} teams", players.len());
+        return Err(PyValueError::new_err(message));
+    }
+
+    Ok(match seed {
+        Some(seed) => Game::with_seed(players, num_teams, seed),
+        None => Game::new(players, num_teams),
+    })
+}
+
+// creates the players of a game; if it has a seed, the random choices of its bots are determined by
+// the seed as well as its deals
+fn create_players(
+    players: &[Bound<'_, PyAny>],
+    shared: &Rc<RefCell<Shared>>,
+    seed: Option<u64>,
+) -> PyResult<Vec<Box<dyn Player>>> {
+    let mut seeds = seed.map(StdRng::seed_from_u64);
+    players.iter()
+        .map(|player| {
+            if let Ok(name) = player.downcast::<PyString>() {
+                named_bot(name.to_str()?, seeds.as_mut())
+            } else if player.hasattr("play")? {
+                let object = player.clone().unbind();
+                Ok(Box::new(PythonPlayer { object, shared: Rc::clone(shared) }) as Box<dyn Player>)
+            } else {
+                Err(PyValueError::new_err("players must be bot names or objects with a play method"))
+            }
+        })
+        .collect()
+}
+
+// creates the bot with the given name, seeded from the given source if there is one
+fn named_bot(name: &str, seeds: Option<&mut StdRng>) -> PyResult<Box<dyn Player>> {
+    let bot = match seeds {
+        Some(seeds) => create_seeded_bot(name, seeds.gen()),
+        None => create_bot(name),
+    };
+    bot.ok_or_else(|| PyValueError::new_err(format!("unknown bot: {name}")))
+}
+
+// calls the Python player's play method and checks the move it returns
+fn choose_move(player: &Bound<'_, PyAny>, team: &Team, hand: &[Card], board: &Board) -> PyResult<Move> {
+    let args = (team.to_string(), cards_to_strings(hand), PyBoard { board: board.clone() });
+    let (card, square): (String, String) = player.call_method1("play", args)?.extract()?;
+    let play = Move::for_card(parse(&card)?, parse(&square)?);
+    play.validate(team, hand, board)
+        .map_err(|error| PyValueError::new_err(format!("illegal move {card} on {square}: {error}")))?;
+    Ok(play)
+}
+
+// a player implemented in Python, as seen by the engine; its moves are chosen by PyGame::run_turn()
+struct PythonPlayer {
+    object: PyObject,
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl PythonPlayer {
+    // calls the given method of the player, if it has one; any error is kept to be raised once the
+    // engine returns, and only the first is kept
+    fn call<T, A, F>(&self, method: &str, args: A, extract: F) -> Option<T>
+    where
+        A: IntoPy<Py<pyo3::types::PyTuple>>,
+        F: FnOnce(&Bound<'_, PyAny>) -> PyResult<T>,
+    {
+        self.try_call(method, args, extract).unwrap_or_else(|error| {
+            self.shared.borrow_mut().error.get_or_insert(error);
+            None
+        })
+    }
+
+    // calls the given method of the player, if it has one, returning any error
+    fn try_call<T, A, F>(&self, method: &str, args: A, extract: F) -> PyResult<Option<T>>
+    where
+        A: IntoPy<Py<pyo3::types::PyTuple>>,
+        F: FnOnce(&Bound<'_, PyAny>) -> PyResult<T>,
+    {
+        Python::with_gil(|py| {
+            let object = self.object.bind(py);
+            if object.hasattr(method)? {
+                object.call_method1(method, args).and_then(|value| extract(&value)).map(Some)
+            } else {
+                Ok(None)
+            }
+        })
+    }
+}
+
+impl Player for PythonPlayer {
+    fn on_game_start(&mut self, seat: usize, team: &Team, _config: &GameConfig) {
+        self.call("on_game_start", (seat, team.to_string()), |_| Ok(()));
+    }
+
+    fn play(&mut self, _team: &Team, _hand: &[Card], _board: &Board, _deck: &Deck) -> Move {
+        self.shared.borrow_mut().play.take().expect("the move is chosen before the turn is run")
+    }
+
+    fn replace_dead_card(&mut self, team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> Option<usize> {
+        let python = Python::with_gil(|py| self.object.bind(py).hasattr("replace_dead_card").unwrap_or(false));
+        if !python {
+            return first_dead_card(hand, board);
+        }
+
+        let args = (team.to_string(), cards_to_strings(hand), PyBoard { board: board.clone() });
+        let choice = self.try_call("replace_dead_card", args, |value| {
+            let index: Option<usize> = value.extract()?;
+            match index {
+                Some(index) if index >= hand.len() || !board.is_dead(&hand[index]) => {
+                    Err(PyValueError::new_err(format!("card {index} in the hand is not dead")))
+                }
+                _ => Ok(index),
+            }
+        });
+
+        // the game only fails if this choice did, not if an earlier callback raised an error
+        choice.map(Option::flatten).unwrap_or_else(|error| {
+            let mut shared = self.shared.borrow_mut();
+            shared.error.get_or_insert(error);
+            shared.failed = true;
+            None
+        })
+    }
+
+    fn on_opponent_move(&mut self, seat: usize, team: &Team, card: &Card, play: &Move, _board: &Board) {
+        let (card, square) = move_tuple(card, play);
+        self.call("on_opponent_move", (seat, team.to_string(), card, square), |_| Ok(()));
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.call("on_game_end", (result.winner.to_string(), result.turns), |_| Ok(()));
+    }
+}
+
+fn cards_to_strings(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
+fn move_tuple(card: &Card, play: &Move) -> (String, String) {
+    (card.to_string(), play.square().to_string())
+}
+
+fn parse<T>(notation: &str) -> PyResult<T> where T: std::str::FromStr<Err = String> {
+    notation.parse().map_err(PyValueError::new_err)
+}
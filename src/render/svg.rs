@@ -0,0 +1,180 @@
+use std::fmt::Write;
+
+use crate::core::board::{Board, BOARD_SIZE};
+use crate::core::square::Square;
+use crate::core::suit::Suit;
+use crate::core::team::Team;
+use crate::history::Frame;
+
+// the size of a square, and of the margin holding the row and column labels, in pixels
+const CELL: u32 = 56;
+const MARGIN: u32 = 24;
+const SIZE: u32 = 2 * MARGIN + CELL * BOARD_SIZE as u32;
+
+// renders the given board as a standalone SVG image: every square with its card, chips in their
+// team's color (outlined in gold when part of a sequence), the last move's square outlined and the
+// highlighted squares (e.g. a card's legal targets) shaded
+pub fn board_svg(board: &Board, last_move: Option<Square>, highlighted: &[Square]) -> String {
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SIZE}" height="{SIZE}" font-family="sans-serif">"#,
+    ).unwrap();
+    writeln!(svg, r#"<rect width="{SIZE}" height="{SIZE}" fill="white"/>"#).unwrap();
+
+    for i in 0..BOARD_SIZE {
+        let center = MARGIN + CELL * i as u32 + CELL / 2;
+        write_text(&mut svg, center, MARGIN / 2, 14, "#000000", &((b'a' + i) as char).to_string());
+        write_text(&mut svg, MARGIN / 2, center, 14, "#000000", &i.to_string());
+    }
+
+    for square in Square::all_squares() {
+        let x = MARGIN + CELL * square.col as u32;
+        let y = MARGIN + CELL * square.row as u32;
+        let fill = if square.is_corner() {
+            "#dddddd"
+        } else if highlighted.contains(&square) {
+            "#ffe680"
+        } else {
+            "#fdfbf5"
+        };
+        writeln!(svg, r##"<rect x="{x}" y="{y}" width="{CELL}" height="{CELL}" fill="{fill}" stroke="#999999"/>"##)
+            .unwrap();
+
+        if let Some(team) = board.chip_at(&square) {
+            let (stroke, width) = if board.in_sequence(&square) { ("#d4a017", 4) } else { ("#333333", 1) };
+            writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}" stroke="{stroke}" stroke-width="{width}"/>"#,
+                x + CELL / 2,
+                y + CELL / 2,
+                CELL / 2 - 6,
+                team_color(team),
+            ).unwrap();
+        }
+
+        if let Some(card) = board.card_at(&square) {
+            let color = match card.suit {
+                Suit::Hearts | Suit::Diamonds => "#c00000",
+                Suit::Spades | Suit::Clubs => "#000000",
+            };
+            write_text(&mut svg, x + CELL / 2, y + CELL / 2, 18, color, &card.to_string());
+        }
+
+        if last_move == Some(square) {
+            writeln!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#ff6600" stroke-width="3"/>"##,
+                x + 2,
+                y + 2,
+                CELL - 4,
+                CELL - 4,
+            ).unwrap();
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// renders each frame of a recorded game (see history.rs) as an SVG image
+pub fn game_svgs(frames: &[Frame]) -> Vec<String> {
+    frames.iter().map(|frame| board_svg(&frame.board, frame.square, &[])).collect()
+}
+
+// renders a recorded game as a standalone HTML page, with a slider (and the arrow keys) to move
+// between its frames and each frame's event shown as a caption
+pub fn game_html(title: &str, frames: &[Frame]) -> String {
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">").unwrap();
+    writeln!(html, "<title>{}</title>", escape(title)).unwrap();
+    writeln!(html, "<style>body {{ font-family: sans-serif; }} .frame {{ display: none; }}</style>").unwrap();
+    writeln!(html, "</head>\n<body>\n<h1>{}</h1>", escape(title)).unwrap();
+    writeln!(
+        html,
+        r#"<input id="slider" type="range" min="0" max="{}" value="0" style="width: {SIZE}px">"#,
+        frames.len().saturating_sub(1),
+    ).unwrap();
+
+    for (i, (frame, svg)) in frames.iter().zip(game_svgs(frames)).enumerate() {
+        writeln!(html, r#"<div class="frame" id="frame-{i}">"#).unwrap();
+        writeln!(html, "<p>{}</p>", escape(&frame.event)).unwrap();
+        html.push_str(&svg);
+        writeln!(html, "</div>").unwrap();
+    }
+
+    html.push_str(r#"<script>
+  const slider = document.getElementById("slider");
+  function show() {
+    document.querySelectorAll(".frame").forEach(frame => frame.style.display = "none");
+    document.getElementById("frame-" + slider.value).style.display = "block";
+  }
+  slider.oninput = show;
+  document.onkeydown = event => {
+    if (event.key === "ArrowLeft" || event.key === "ArrowRight") {
+      slider.value = Number(slider.value) + (event.key === "ArrowLeft" ? -1 : 1);
+      show();
+    }
+  };
+  show();
+</script>
+</body>
+</html>
+"#);
+    html
+}
+
+// writes text centered on the given point
+fn write_text(svg: &mut String, x: u32, y: u32, size: u32, color: &str, text: &str) {
+    write!(svg, r#"<text x="{x}" y="{y}" font-size="{size}" fill="{color}" "#).unwrap();
+    writeln!(svg, r#"text-anchor="middle" dominant-baseline="central">{}</text>"#, escape(text)).unwrap();
+}
+
+fn team_color(team: Team) -> &'static str {
+    match team {
+        Team::One => "#4a7fdc",
+        Team::Two => "#3fa35a",
+        Team::Three => "#d9534f",
+    }
+}
+
+// escapes text for use in HTML or SVG
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::Game;
+    use crate::players::random_player::RandomPlayer;
+
+    use super::*;
+
+    #[test]
+    fn renders_boards_and_games() {
+        let mut board = Board::standard_board();
+        let e4 = "e4".parse().unwrap();
+        board.add_chip(&e4, Team::One);
+        board.add_chip(&"e5".parse().unwrap(), Team::Two);
+
+        let svg = board_svg(&board, Some(e4), &["a1".parse().unwrap()]);
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<circle").count(), 2);
+        assert_eq!(svg.matches("#ff6600").count(), 1);
+        assert_eq!(svg.matches("#ffe680").count(), 1);
+        assert!(svg.contains(&format!(">{}</text>", board.card_at(&e4).unwrap())));
+
+        let mut game = Game::new(vec!(Box::new(RandomPlayer::default()), Box::new(RandomPlayer::default())), 2);
+        let mut frames = vec![Frame::start(&game, None)];
+        for _ in 0..3 {
+            let (seat, board, history) = (game.up_index(), game.board().clone(), game.deck().history().len());
+            let result = game.run_turn();
+            frames.push(Frame::after_turn(&game, seat, &board, history, None, result));
+        }
+
+        let html = game_html("A <random> game", &frames);
+        assert_eq!(html.matches("<svg").count(), 4);
+        assert!(html.contains("<title>A &lt;random&gt; game</title>"));
+        assert!(html.contains(r#"max="3""#));
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use std::env;
+use std::fmt::{Display, Formatter, Result};
+
+use crate::core::board::{Board, BOARD_SIZE};
+use crate::core::card::Card;
+use crate::core::square::Square;
+use crate::core::team::Team;
+
+// colors for the teams' chips and for highlighted cards, as indexes into the terminal's 256-color
+// palette (where 0-7 are the standard colors)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Palette {
+    pub teams: [u8; 3],
+    pub highlight: u8,
+}
+
+impl Palette {
+    // blue, green and red chips, with yellow highlights
+    pub const STANDARD: Palette = Palette { teams: [4, 2, 1], highlight: 3 };
+    // blue, orange and pink chips, which are easier to tell apart with red-green color blindness
+    pub const COLOR_BLIND: Palette = Palette { teams: [33, 214, 170], highlight: 226 };
+
+    fn team_color(&self, team: Team) -> u8 {
+        match team {
+            Team::One => self.teams[0],
+            Team::Two => self.teams[1],
+            Team::Three => self.teams[2],
+        }
+    }
+
+    fn paint(color: u8, text: &str, background: bool) -> String {
+        let layer = if background { 48 } else { 38 };
+        format!("\x1b[{layer};5;{color}m{text}\x1b[39;49m")
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TextOptions {
+    // the colors to use, or None for plain text which relies on the symbols alone
+    pub palette: Option<Palette>,
+    // show only the chips, one character per square, instead of every square's card
+    pub compact: bool,
+    // the cards whose empty squares are marked, e.g. those in a player's hand
+    pub highlighted_cards: HashSet<Card>,
+}
+
+impl TextOptions {
+    // the options chosen by the BOARD_STYLE environment variable, a comma-separated list of "plain"
+    // (no colors), "color-blind" (the COLOR_BLIND palette) and "compact"; boards are in the standard
+    // palette by default, or plain if the NO_COLOR environment variable is set (see no-color.org)
+    pub fn from_env() -> TextOptions {
+        let no_color = env::var_os("NO_COLOR").map_or(false, |value| !value.is_empty());
+        TextOptions::from_style(&env::var("BOARD_STYLE").unwrap_or_default(), no_color)
+    }
+
+    fn from_style(style: &str, no_color: bool) -> TextOptions {
+        let mut options = TextOptions {
+            palette: if no_color { None } else { Some(Palette::STANDARD) },
+            ..Default::default()
+        };
+        for word in style.split(',').map(|word| word.trim()) {
+            match word {
+                "plain" => options.palette = None,
+                "color-blind" if !no_color => options.palette = Some(Palette::COLOR_BLIND),
+                "compact" => options.compact = true,
+                _ => {}
+            }
+        }
+        options
+    }
+}
+
+// renders a board as text through Display, e.g. with print!("{}", BoardText::new(&board, &options))
+//
+// every chip is shown by its team's initial (b, g or r), in upper case if it is part of a sequence,
+// so boards stay readable without colors; empty squares are shown as . (or ? if highlighted) and
+// the corners as +
+pub struct BoardText<'a> {
+    board: &'a Board,
+    options: &'a TextOptions,
+}
+
+impl<'a> BoardText<'a> {
+    pub fn new(board: &'a Board, options: &'a TextOptions) -> BoardText<'a> {
+        BoardText { board, options }
+    }
+
+    fn is_highlighted(&self, square: &Square) -> bool {
+        self.board.card_at(square).map_or(false, |card| self.options.highlighted_cards.contains(&card))
+    }
+
+    // the symbol for the given square, colored by the palette if there is one
+    fn symbol(&self, square: &Square) -> String {
+        let symbol = match self.board.chip_at(square) {
+            Some(team) => chip_symbol(team, self.board.in_sequence(square)),
+            None if square.is_corner() => '+',
+            None if self.is_highlighted(square) => '?',
+            None => '.',
+        };
+        self.paint(square, &symbol.to_string())
+    }
+
+    // colors the given text for the given square, if there is a palette
+    fn paint(&self, square: &Square, text: &str) -> String {
+        let Some(palette) = self.options.palette else { return String::from(text) };
+
+        match self.board.chip_at(square) {
+            Some(team) => Palette::paint(palette.team_color(team), text, self.board.in_sequence(square)),
+            None if self.is_highlighted(square) => Palette::paint(palette.highlight, text, false),
+            None => String::from(text),
+        }
+    }
+}
+
+impl Display for BoardText<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.options.compact {
+            write!(f, "  ")?;
+            for col in 0..BOARD_SIZE {
+                write!(f, " {}", (b'a' + col) as char)?;
+            }
+            writeln!(f)?;
+
+            for row in 0..BOARD_SIZE {
+                write!(f, "{row} ")?;
+                for col in 0..BOARD_SIZE {
+                    write!(f, " {}", self.symbol(&Square { row, col }))?;
+                }
+                writeln!(f)?;
+            }
+            return Ok(());
+        }
+
+        for row in 0..BOARD_SIZE {
+            for col in 0..BOARD_SIZE {
+                let square = Square { row, col };
+                let card = self.board.card_at(&square).map_or(String::from("--"), |card| card.to_string());
+                let text = format!("{square} {card}");
+                write!(f, "| {} {} ", self.paint(&square, &text), self.symbol(&square))?;
+            }
+            writeln!(f, "|")?;
+        }
+        Ok(())
+    }
+}
+
+// the symbol for a chip of the given team: its initial, in upper case if the chip is in a sequence
+pub fn chip_symbol(team: Team, in_sequence: bool) -> char {
+    let symbol = match team {
+        Team::One => 'b',
+        Team::Two => 'g',
+        Team::Three => 'r',
+    };
+    if in_sequence { symbol.to_ascii_uppercase() } else { symbol }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_with_and_without_colors() {
+        let mut board = Board::standard_board();
+        for square in ["b1", "c1", "d1", "e1", "f1"] {
+            board.add_chip(&square.parse().unwrap(), Team::One);
+        }
+        board.add_chip(&"e5".parse().unwrap(), Team::Two);
+        let e6 = "e6".parse().unwrap();
+        let highlighted_cards = HashSet::from([board.card_at(&e6).unwrap()]);
+
+        let plain = TextOptions { highlighted_cards: highlighted_cards.clone(), ..Default::default() };
+        let text = BoardText::new(&board, &plain).to_string();
+        assert!(!text.contains('\x1b'));
+        assert_eq!(text.lines().count(), BOARD_SIZE as usize);
+        assert!(text.contains(&format!("| e5 {} g |", board.card_at(&"e5".parse().unwrap()).unwrap())));
+        assert!(text.contains(&format!("| e6 {} ? |", board.card_at(&e6).unwrap())));
+        assert_eq!(text.matches(" B |").count(), 5);
+
+        let compact = TextOptions { compact: true, ..Default::default() };
+        let text = BoardText::new(&board, &compact).to_string();
+        assert_eq!(text.lines().nth(2).unwrap(), "1  . B B B B B . . . .");
+        assert_eq!(text.lines().nth(1).unwrap(), "0  + . . . . . . . . +");
+
+        let colored = TextOptions { highlighted_cards, ..TextOptions::from_style("compact, color-blind", false) };
+        let text = BoardText::new(&board, &colored).to_string();
+        assert_eq!(text.matches("\x1b[48;5;33mB").count(), 5);
+        assert!(text.contains("\x1b[38;5;214mg"));
+        assert!(text.contains("\x1b[38;5;226m?"));
+
+        assert_eq!(TextOptions::from_style("", false).palette, Some(Palette::STANDARD));
+        assert_eq!(TextOptions::from_style("color-blind", true).palette, None);
+        assert_eq!(TextOptions::from_style("plain", false).palette, None);
+    }
+}
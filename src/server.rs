@@ -0,0 +1,621 @@
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+use rand::Rng;
+use serde_json::{json, Map, Value};
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::Deck;
+use crate::core::game::{Game, GameConfig, GameResult};
+use crate::core::moves::{legal_moves, Move};
+use crate::core::player::Player;
+use crate::core::square::Square;
+use crate::core::team::Team;
+
+// hosts a single game for clients connecting over TCP, e.g. humans in their own terminals or bots;
+// every message is a JSON object on a single line with a "type" field, where cards are formatted as
+// by their Display (e.g. "T♠", although "Ts" is also accepted), squares as e.g. "e4" and teams by
+// their name (e.g. "BLUE")
+//
+// messages from a client to the server:
+// - {"type":"join","name":...}: the first message on a new connection, taking the next free seat
+// - {"type":"rejoin","seat":...,"token":...}: the first message on a new connection, retaking a
+//   seat after being disconnected with the token given when joining
+// - {"type":"move","card":...,"square":...}: in reply to your_turn; the kind of move is determined
+//   by the card (two-eyed jacks place a chip anywhere, one-eyed jacks remove one)
+// - {"type":"exchange","card":...} or {"type":"keep"}: in reply to dead_card
+//
+// messages from the server to a client:
+// - welcome: seat, team, token, players, teams; in reply to join or rejoin (followed by the last
+//   state sent to the seat, if any, on rejoin)
+// - start: seat, team, players, teams, hand_size, winning_sequences, max_dead_card_exchanges and
+//   cards (the cards on the board, indexed by row and then column; null for corners)
+// - state: hand (the client's own hand, which is never sent to anyone else), chips (an object from
+//   square to team), sequences (each with team and squares), draw_pile (its size) and discard_pile
+// - your_turn: legal_moves (each with card and square); sent after state, the client replies move
+// - dead_card: dead_cards; sent after state, the client replies exchange or keep
+// - move: seat, team, card, square and kind (place, wild_place or remove), for every move
+// - dead: seat, team, card, when another player exchanges a dead card
+// - skip: seat, team, when another player has no playable card
+// - game_over: winner, turns; the connection is then closed
+// - error: message, e.g. for an illegal move (after which the client should reply again), or a
+//   failed join or rejoin (after which the connection is closed)
+//
+// a client which disconnects can rejoin at any time; the game waits for them if it is their turn,
+// and they miss any events while disconnected (except for the state, which is resent)
+pub struct Server {
+    listener: TcpListener,
+    num_players: usize,
+    num_teams: usize,
+    seats: Arc<Seats>,
+}
+
+// the seats of the game, shared between the threads accepting connections and the game itself
+struct Seats {
+    state: Mutex<SeatsState>,
+    changed: Condvar,
+}
+
+struct SeatsState {
+    seats: Vec<Seat>,
+    // set once the game is over, after which connections are no longer accepted
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Seat {
+    // the name of the client which joined in this seat, or None if it is free
+    name: Option<String>,
+    token: String,
+    // the connection of the client in this seat, or None if they are disconnected
+    stream: Option<TcpStream>,
+    // the reader for a new connection, until it is taken by the seat's RemotePlayer
+    reader: Option<BufReader<TcpStream>>,
+    // incremented on every (re)connection, so that a replaced connection can be told apart
+    generation: usize,
+    // the last state message sent to the client, resent when they rejoin
+    snapshot: Option<String>,
+}
+
+impl Server {
+    // listens on the given address for clients to play a game with the given number of players and
+    // teams
+    pub fn bind<A: ToSocketAddrs>(address: A, num_players: usize, num_teams: usize) -> io::Result<Server> {
+        if !Game::is_supported(num_players, num_teams) {
+            let message = format!("unsupported game: {num_players} players in {num_teams} teams");
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+
+        let seats = SeatsState {
+            seats: (0..num_players).map(|_| Seat::default()).collect(),
+            closed: false,
+        };
+
+        Ok(Server {
+            listener: TcpListener::bind(address)?,
+            num_players,
+            num_teams,
+            seats: Arc::new(Seats { state: Mutex::new(seats), changed: Condvar::new() }),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // waits for every seat to be taken, then plays the game and returns its result once it is over
+    pub fn run(self) -> io::Result<GameResult> {
+        let mut address = self.listener.local_addr()?;
+        let listener = self.listener.try_clone()?;
+        let seats = Arc::clone(&self.seats);
+        let num_teams = self.num_teams;
+        let acceptor = thread::spawn(move || accept_connections(listener, seats, num_teams));
+
+        {
+            let mut state = self.seats.state.lock().unwrap();
+            while state.seats.iter().any(|seat| seat.name.is_none()) {
+                state = self.seats.changed.wait(state).unwrap();
+            }
+        }
+
+        let players: Vec<Box<dyn Player>> = (0..self.num_players)
+            .map(|seat| {
+                let player = RemotePlayer { seat, seats: Arc::clone(&self.seats), connection: None };
+                Box::new(player) as Box<dyn Player>
+            })
+            .collect();
+        let result = Game::new(players, self.num_teams).run();
+
+        {
+            let mut state = self.seats.state.lock().unwrap();
+            state.closed = true;
+            for seat in &mut state.seats {
+                if let Some(stream) = seat.stream.take() {
+                    let _ = stream.shutdown(Shutdown::Both);
+                }
+            }
+        }
+
+        // wake up the acceptor, which is waiting for a connection, so that it sees the game is over
+        if address.ip().is_unspecified() {
+            address.set_ip(Ipv4Addr::LOCALHOST.into());
+        }
+        let _ = TcpStream::connect(address);
+        let _ = acceptor.join();
+
+        Ok(result)
+    }
+}
+
+fn accept_connections(listener: TcpListener, seats: Arc<Seats>, num_teams: usize) {
+    for stream in listener.incoming() {
+        if seats.state.lock().unwrap().closed {
+            break;
+        }
+
+        // each client is greeted on its own thread so that a slow one does not hold up the others
+        if let Ok(stream) = stream {
+            let seats = Arc::clone(&seats);
+            thread::spawn(move || greet(stream, &seats, num_teams));
+        }
+    }
+}
+
+// handles the join or rejoin message on a new connection, handing it over to its seat on success
+fn greet(stream: TcpStream, seats: &Seats, num_teams: usize) -> io::Result<()> {
+    // messages are small and interactive, so should not wait to be batched
+    stream.set_nodelay(true)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream.try_clone()?;
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let message: Value = serde_json::from_str(&line).unwrap_or_default();
+
+    let mut state = seats.state.lock().unwrap();
+    let seat_index = if state.closed {
+        Err(String::from("the game is over"))
+    } else {
+        match message["type"].as_str() {
+            Some("join") => state.seats.iter()
+                .position(|seat| seat.name.is_none())
+                .ok_or_else(|| String::from("the game is full; rejoin with a seat and token to take a seat back")),
+            Some("rejoin") => message["seat"].as_u64()
+                .map(|seat| seat as usize)
+                .filter(|seat| {
+                    state.seats.get(*seat).map_or(false, |seat| {
+                        seat.name.is_some() && message["token"].as_str() == Some(seat.token.as_str())
+                    })
+                })
+                .ok_or_else(|| String::from("invalid seat or token")),
+            _ => Err(String::from("expected join or rejoin")),
+        }
+    };
+
+    let seat_index = match seat_index {
+        Ok(seat_index) => seat_index,
+        Err(error) => return send(&mut writer, &[json!({ "type": "error", "message": error })]),
+    };
+
+    let seat = &mut state.seats[seat_index];
+    if seat.name.is_none() {
+        seat.name = Some(message["name"].as_str().unwrap_or_default().to_string());
+        seat.token = format!("{:016x}", rand::thread_rng().gen::<u64>());
+    }
+
+    // replace any previous connection, which also interrupts the seat's player if it is waiting on it
+    if let Some(previous) = seat.stream.replace(stream) {
+        let _ = previous.shutdown(Shutdown::Both);
+    }
+    seat.reader = Some(reader);
+    seat.generation += 1;
+
+    // the welcome is sent while still holding the lock so that it is received before anything sent
+    // by the game
+    let mut messages = vec![json!({
+        "type": "welcome",
+        "seat": seat_index,
+        "team": Game::player_team(num_teams, seat_index).to_string(),
+        "token": seat.token,
+        "players": state.seats.len(),
+        "teams": num_teams,
+    })];
+    if let Some(snapshot) = &state.seats[seat_index].snapshot {
+        messages.push(serde_json::from_str(snapshot).unwrap_or_default());
+    }
+    let sent = send(&mut writer, &messages);
+
+    seats.changed.notify_all();
+    sent
+}
+
+// sends the given messages in a single write, since they are usually answered straight away
+fn send(writer: &mut TcpStream, messages: &[Value]) -> io::Result<()> {
+    let lines: String = messages.iter().map(|message| format!("{message}\n")).collect();
+    writer.write_all(lines.as_bytes())?;
+    writer.flush()
+}
+
+// the connection used by a RemotePlayer, taken from its seat
+struct Connection {
+    generation: usize,
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+// player for a seat taken by a client, sending them events and waiting for their decisions
+struct RemotePlayer {
+    seat: usize,
+    seats: Arc<Seats>,
+    connection: Option<Connection>,
+}
+
+impl RemotePlayer {
+    // returns the current connection of the client in this seat, taking over a new one if they
+    // have rejoined; if they are disconnected, waits for them to rejoin if wait is true and
+    // otherwise returns None
+    fn connect(&mut self, wait: bool) -> Option<&mut Connection> {
+        let mut state = self.seats.state.lock().unwrap();
+        loop {
+            let seat = &mut state.seats[self.seat];
+            if let Some(stream) = &seat.stream {
+                let generation = self.connection.as_ref().map(|connection| connection.generation);
+                if generation == Some(seat.generation) {
+                    break;
+                }
+
+                if let (Some(reader), Ok(writer)) = (seat.reader.take(), stream.try_clone()) {
+                    self.connection = Some(Connection { generation: seat.generation, reader, writer });
+                    break;
+                }
+                seat.stream = None;
+            }
+
+            self.connection = None;
+            if !wait || state.closed {
+                return None;
+            }
+            state = self.seats.changed.wait(state).unwrap();
+        }
+
+        self.connection.as_mut()
+    }
+
+    // forgets the current connection after a failure, marking the client as disconnected unless
+    // they have already rejoined
+    fn disconnect(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            let mut state = self.seats.state.lock().unwrap();
+            let seat = &mut state.seats[self.seat];
+            if seat.generation == connection.generation {
+                seat.stream = None;
+            }
+        }
+    }
+
+    // sends the given messages, returning false if the client is (or becomes) disconnected
+    fn send(&mut self, messages: &[Value], wait: bool) -> bool {
+        let Some(connection) = self.connect(wait) else { return false };
+        if send(&mut connection.writer, messages).is_err() {
+            self.disconnect();
+            return false;
+        }
+        true
+    }
+
+    // sends the given event, which the client misses if they are disconnected
+    fn event(&mut self, message: Value) {
+        self.send(&[message], false);
+    }
+
+    // sends the state and then the given prompt, returning the first reply accepted by the given
+    // parser; invalid replies are answered with an error, and the state and prompt are resent if the
+    // client reconnects
+    fn request<T, F>(&mut self, state: Value, prompt: Value, mut parse: F) -> T
+        where F: FnMut(&Value) -> Result<T, String> {
+        self.seats.state.lock().unwrap().seats[self.seat].snapshot = Some(state.to_string());
+
+        let messages = [state, prompt];
+        loop {
+            if !self.send(&messages, true) {
+                continue;
+            }
+
+            loop {
+                let mut line = String::new();
+                let read = self.connection.as_mut().map(|connection| connection.reader.read_line(&mut line));
+                if !matches!(read, Some(Ok(length)) if length > 0) {
+                    self.disconnect();
+                    break;
+                }
+
+                let reply = serde_json::from_str(&line)
+                    .map_err(|error| format!("invalid JSON: {error}"))
+                    .and_then(|message| parse(&message));
+                match reply {
+                    Ok(reply) => return reply,
+                    Err(error) => self.event(json!({ "type": "error", "message": error })),
+                }
+            }
+        }
+    }
+}
+
+impl Player for RemotePlayer {
+    fn on_game_start(&mut self, seat: usize, team: &Team, config: &GameConfig) {
+        self.event(json!({
+            "type": "start",
+            "seat": seat,
+            "team": team.to_string(),
+            "players": config.num_players,
+            "teams": config.num_teams,
+            "hand_size": config.hand_size,
+            "winning_sequences": config.winning_sequences,
+            "max_dead_card_exchanges": config.max_dead_card_exchanges,
+            "cards": cards_json(&Board::standard_board()),
+        }));
+    }
+
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+        let moves: Vec<Value> = legal_moves(team, hand, board).iter()
+            .filter_map(|play| play.card_index(hand).map(|index| move_json(&hand[index], play)))
+            .collect();
+        let prompt = json!({ "type": "your_turn", "legal_moves": moves });
+
+        let (card, play) = self.request(state_json(hand, board, deck), prompt, |message| {
+            let (Some("move"), Some(card), Some(square)) =
+                (message["type"].as_str(), message["card"].as_str(), message["square"].as_str()) else {
+                return Err(String::from("expected a move with a card and a square"));
+            };
+
+            let card: Card = card.parse()?;
+            let play = Move::for_card(card, square.parse()?);
+            play.validate(team, hand, board)?;
+            Ok((card, play))
+        });
+
+        self.event(event_json("move", self.seat, team, &card, Some(&play)));
+        play
+    }
+
+    fn replace_dead_card(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Option<usize> {
+        let dead_cards: Vec<String> = hand.iter()
+            .filter(|card| board.is_dead(card))
+            .map(|card| card.to_string())
+            .collect();
+        let prompt = json!({ "type": "dead_card", "dead_cards": dead_cards });
+
+        let index = self.request(state_json(hand, board, deck), prompt, |message| {
+            match (message["type"].as_str(), message["card"].as_str()) {
+                (Some("keep"), _) => Ok(None),
+                (Some("exchange"), Some(card)) => {
+                    let card: Card = card.parse()?;
+                    hand.iter()
+                        .position(|hand_card| *hand_card == card && board.is_dead(hand_card))
+                        .map(Some)
+                        .ok_or_else(|| format!("{card} is not a dead card in your hand"))
+                }
+                _ => Err(String::from("expected exchange with a card, or keep")),
+            }
+        });
+
+        if let Some(index) = index {
+            self.event(event_json("dead", self.seat, team, &hand[index], None));
+        }
+        index
+    }
+
+    fn on_opponent_move(&mut self, seat: usize, team: &Team, card: &Card, play: &Move, _board: &Board) {
+        self.event(event_json("move", seat, team, card, Some(play)));
+    }
+
+    fn on_opponent_dead_card(&mut self, seat: usize, team: &Team, card: &Card) {
+        self.event(event_json("dead", seat, team, card, None));
+    }
+
+    fn on_opponent_skip(&mut self, seat: usize, team: &Team, _board: &Board) {
+        self.event(json!({ "type": "skip", "seat": seat, "team": team.to_string() }));
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.event(json!({ "type": "game_over", "winner": result.winner.to_string(), "turns": result.turns }));
+    }
+}
+
+// returns the state of the game as seen by the player with the given hand
+fn state_json(hand: &[Card], board: &Board, deck: &Deck) -> Value {
+    let mut state = public_state_json(board, deck);
+    state["type"] = json!("state");
+    state["hand"] = json!(hand.iter().map(|card| card.to_string()).collect::<Vec<String>>());
+    state
+}
+
+// returns the state of the game which is visible to every player: chips (an object from square to
+// team), sequences (each with team and squares), draw_pile (its size) and discard_pile
+pub fn public_state_json(board: &Board, deck: &Deck) -> Value {
+    let chips: Map<String, Value> = Square::all_squares()
+        .filter_map(|square| board.chip_at(&square).map(|team| (square.to_string(), json!(team.to_string()))))
+        .collect();
+
+    let sequences: Vec<Value> = board.sequences().iter()
+        .map(|(team, squares)| {
+            let mut squares: Vec<String> = squares.iter().map(|square| square.to_string()).collect();
+            squares.sort();
+            json!({ "team": team.to_string(), "squares": squares })
+        })
+        .collect();
+
+    json!({
+        "chips": chips,
+        "sequences": sequences,
+        "draw_pile": deck.draw_pile_size(),
+        "discard_pile": deck.discard_pile().iter().map(|card| card.to_string()).collect::<Vec<String>>(),
+    })
+}
+
+// returns the cards on the given board, indexed by row and then column (null for corners)
+pub fn cards_json(board: &Board) -> Value {
+    let cards: Vec<Vec<Option<String>>> = board.cards().iter()
+        .map(|row| row.iter().map(|card| card.map(|card| card.to_string())).collect())
+        .collect();
+    json!(cards)
+}
+
+pub fn move_json(card: &Card, play: &Move) -> Value {
+    json!({ "card": card.to_string(), "square": play.square().to_string() })
+}
+
+// returns a move event (with the given move) or a dead card event (without one)
+fn event_json(event: &str, seat: usize, team: &Team, card: &Card, play: Option<&Move>) -> Value {
+    let mut message = json!({ "type": event, "seat": seat, "team": team.to_string(), "card": card.to_string() });
+    if let Some(play) = play {
+        message["square"] = json!(play.square().to_string());
+        message["kind"] = json!(match play {
+            Move::Place { .. } => "place",
+            Move::WildPlace { .. } => "wild_place",
+            Move::Remove { .. } => "remove",
+        });
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    // a scripted client connected to a server
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+    }
+
+    impl Client {
+        fn connect(address: SocketAddr, message: Value) -> Client {
+            let stream = TcpStream::connect(address).unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(30))).unwrap();
+            stream.set_nodelay(true).unwrap();
+            let mut client = Client { reader: BufReader::new(stream.try_clone().unwrap()), writer: stream };
+            client.send(message);
+            client
+        }
+
+        fn send(&mut self, message: Value) {
+            writeln!(self.writer, "{message}").unwrap();
+        }
+
+        // returns the next message, or None if the server closed the connection
+        fn receive(&mut self) -> Option<Value> {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) | Err(_) => None,
+                Ok(_) => Some(serde_json::from_str(&line).unwrap()),
+            }
+        }
+    }
+
+    // how a scripted client misbehaves, to exercise the server
+    #[derive(Clone, Copy)]
+    struct Script {
+        // rejoin on a new connection after making the first move
+        reconnect: bool,
+        // reply to the first prompt with a move which is illegal
+        illegal_move: bool,
+    }
+
+    // plays the game as a client always making the first legal move (and exchanging any dead card,
+    // since keeping them could leave every player skipping forever), returning the game_over message
+    fn play(address: SocketAddr, mut client: Client, welcome: Value, script: Script) -> Value {
+        let (mut reconnect, mut illegal_move) = (script.reconnect, script.illegal_move);
+        let hand_size = Game::hand_size(welcome["players"].as_u64().unwrap() as usize);
+
+        loop {
+            let message = client.receive().expect("disconnected before the end of the game");
+            match message["type"].as_str().unwrap() {
+                "state" => assert_eq!(message["hand"].as_array().unwrap().len(), hand_size),
+                "your_turn" => {
+                    let first = &message["legal_moves"][0];
+                    if illegal_move {
+                        illegal_move = false;
+                        client.send(json!({ "type": "move", "card": first["card"], "square": "a0" }));
+                        let error = client.receive().unwrap();
+                        assert_eq!(error["type"], "error");
+                    }
+                    client.send(json!({ "type": "move", "card": first["card"], "square": first["square"] }));
+                }
+                "dead_card" => client.send(json!({ "type": "exchange", "card": message["dead_cards"][0] })),
+                "move" if reconnect && message["seat"] == welcome["seat"] => {
+                    reconnect = false;
+                    drop(client);
+                    client = Client::connect(
+                        address,
+                        json!({ "type": "rejoin", "seat": welcome["seat"], "token": welcome["token"] }),
+                    );
+                    let rejoined = client.receive().unwrap();
+                    assert_eq!(rejoined["type"], "welcome");
+                    assert_eq!(rejoined["seat"], welcome["seat"]);
+                }
+                "game_over" => return message,
+                "error" => panic!("unexpected error: {message}"),
+                _ => {}
+            }
+        }
+    }
+
+    // hosts a game for the given scripted clients, returning the result and each game_over message
+    fn host(num_teams: usize, scripts: &[Script]) -> (GameResult, Vec<Value>) {
+        let server = Server::bind("127.0.0.1:0", scripts.len(), num_teams).unwrap();
+        let address = server.local_addr().unwrap();
+        let server = thread::spawn(move || server.run());
+
+        let clients: Vec<_> = scripts.iter()
+            .enumerate()
+            .map(|(seat, script)| {
+                let mut client = Client::connect(address, json!({ "type": "join", "name": format!("client {seat}") }));
+                let welcome = client.receive().unwrap();
+                assert_eq!(welcome["seat"], seat);
+                assert_eq!(welcome["team"], Game::player_team(num_teams, seat).to_string());
+
+                let script = *script;
+                thread::spawn(move || play(address, client, welcome, script))
+            })
+            .collect();
+
+        // every seat is taken, so any further client is turned away
+        let mut extra = Client::connect(address, json!({ "type": "join", "name": "extra" }));
+        assert_eq!(extra.receive().unwrap()["type"], "error");
+        let mut impostor = Client::connect(address, json!({ "type": "rejoin", "seat": 0, "token": "guess" }));
+        assert_eq!(impostor.receive().unwrap()["type"], "error");
+
+        let game_overs = clients.into_iter().map(|client| client.join().unwrap()).collect();
+        (server.join().unwrap().unwrap(), game_overs)
+    }
+
+    #[test]
+    fn plays_a_game_with_reconnecting_and_misbehaving_clients() {
+        let scripts = [
+            Script { reconnect: true, illegal_move: false },
+            Script { reconnect: false, illegal_move: true },
+        ];
+        let (result, game_overs) = host(2, &scripts);
+
+        for game_over in game_overs {
+            assert_eq!(game_over["winner"], result.winner.to_string());
+            assert_eq!(game_over["turns"], result.turns);
+        }
+    }
+
+    #[test]
+    fn assigns_seats_to_teams() {
+        let script = Script { reconnect: false, illegal_move: false };
+        let (result, game_overs) = host(2, &[script; 4]);
+
+        assert_eq!(game_overs.len(), 4);
+        assert!(result.winner == Team::One || result.winner == Team::Two);
+    }
+}
@@ -0,0 +1,256 @@
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::Deck;
+use crate::core::game::Game;
+use crate::core::moves::{legal_moves, Move};
+use crate::core::player::Player;
+use crate::core::team::Team;
+use crate::evaluators::linear_evaluator::{extract_features, LinearEvaluator, NUM_FEATURES};
+use crate::log::LogLevel;
+use crate::players::learned_player::LearnedPlayer;
+use crate::players::square_evaluation_player::SquareEvaluationPlayer;
+use crate::util::params::Parameterized;
+
+pub struct TrainerOptions {
+    // number of rounds of self-play followed by training
+    pub generations: usize,
+    pub games_per_generation: usize,
+    // number of passes over each generation's positions
+    pub epochs: usize,
+    pub learning_rate: f32,
+    // TD(lambda) parameter: 0 trains each position towards the evaluation of the team's next
+    // position, 1 trains each position towards the final outcome
+    pub lambda: f32,
+    // probability that a self-play move is chosen uniformly at random instead of greedily
+    pub exploration: f32,
+    // number of games played against a default SquareEvaluationPlayer after each generation to
+    // measure progress; 0 to skip
+    pub benchmark_games: usize,
+}
+
+impl Default for TrainerOptions {
+    fn default() -> Self {
+        TrainerOptions {
+            generations: 20,
+            games_per_generation: 100,
+            epochs: 3,
+            learning_rate: 0.01,
+            lambda: 0.7,
+            exploration: 0.1,
+            benchmark_games: 50,
+        }
+    }
+}
+
+// the positions (as features) reached by a team after each of its moves in a game, and whether the
+// team won
+struct TeamTrajectory {
+    positions: Vec<[f32; NUM_FEATURES]>,
+    won: bool,
+}
+
+// learns the weights of a LinearEvaluator by TD(lambda) on games of self-play: the evaluation of a
+// position is the log-odds that the team which just moved wins, and after each batch of games
+// every position is trained towards its lambda-return (a blend of the evaluations of the team's
+// later positions and the final outcome) by gradient descent on the cross-entropy loss
+pub struct Trainer {
+    options: TrainerOptions,
+    evaluator: LinearEvaluator,
+    // win rate against SquareEvaluationPlayer after each generation, if benchmarked
+    benchmarks: Vec<f32>,
+}
+
+impl Trainer {
+    pub fn new(options: TrainerOptions, initial: LinearEvaluator) -> Trainer {
+        Trainer { options, evaluator: initial, benchmarks: vec![] }
+    }
+
+    pub fn run(&mut self) -> &LinearEvaluator {
+        for generation in 0..self.options.generations {
+            let trajectories: Vec<TeamTrajectory> = (0..self.options.games_per_generation)
+                .flat_map(|_| self.play_game())
+                .collect();
+
+            for _ in 0..self.options.epochs {
+                for trajectory in &trajectories {
+                    self.train(trajectory);
+                }
+            }
+
+            if self.options.benchmark_games > 0 {
+                let win_rate = self.benchmark();
+                self.benchmarks.push(win_rate);
+                LogLevel::Results.log(
+                    &format!("Training generation {}/{}: win rate {:.3} against SquareEvaluationPlayer",
+                             generation + 1, self.options.generations, win_rate),
+                );
+            } else {
+                LogLevel::Results.log(
+                    &format!("Training generation {}/{}", generation + 1, self.options.generations),
+                );
+            }
+        }
+
+        &self.evaluator
+    }
+
+    pub fn evaluator(&self) -> &LinearEvaluator {
+        &self.evaluator
+    }
+
+    pub fn benchmarks(&self) -> &[f32] {
+        &self.benchmarks
+    }
+
+    // writes the learned weights to the given file, one per line, in a format readable by
+    // Parameterized::from_param_file() (for either LinearEvaluator or LearnedPlayer)
+    pub fn write_weights<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let contents: String = self.evaluator.params().iter()
+            .map(|(name, value)| format!("{name}={value}\n"))
+            .collect();
+        fs::write(path, contents)
+    }
+
+    // plays a game of self-play with the current weights, returning the positions reached by each
+    // team
+    fn play_game(&self) -> Vec<TeamTrajectory> {
+        let positions: Vec<Positions> = (0..2).map(|_| Positions::default()).collect();
+        let players: Vec<Box<dyn Player>> = positions.iter()
+            .map(|positions| {
+                let player = SelfPlayPlayer {
+                    player: self.learned_player(),
+                    exploration: self.options.exploration,
+                    positions: Rc::clone(positions),
+                };
+                Box::new(player) as Box<dyn Player>
+            })
+            .collect();
+        let winner = Game::new(players, 2).run().winner;
+
+        positions.into_iter()
+            .enumerate()
+            .map(|(team_index, positions)| {
+                TeamTrajectory { positions: positions.take(), won: Game::player_team(2, team_index) == winner }
+            })
+            .collect()
+    }
+
+    // applies one step of gradient descent towards the lambda-return of each position
+    fn train(&mut self, trajectory: &TeamTrajectory) {
+        let outcome = if trajectory.won { 1.0 } else { 0.0 };
+        let lambda = self.options.lambda;
+
+        // lambda-returns, computed backwards from the outcome with the weights before this step
+        let mut targets = vec![0.0; trajectory.positions.len()];
+        let mut target = outcome;
+        for (index, position) in trajectory.positions.iter().enumerate().rev() {
+            targets[index] = target;
+            let value = self.evaluator.win_probability(position);
+            target = (1.0 - lambda) * value + lambda * target;
+        }
+
+        for (position, target) in trajectory.positions.iter().zip(targets) {
+            let error = self.evaluator.win_probability(position) - target;
+            for (weight, feature) in self.evaluator.weights.iter_mut().zip(position) {
+                *weight -= self.options.learning_rate * error * feature;
+            }
+        }
+    }
+
+    // returns the win rate of the current weights against a default SquareEvaluationPlayer
+    fn benchmark(&self) -> f32 {
+        let mut wins = 0;
+        for game in 0..self.options.benchmark_games {
+            let learned: Box<dyn Player> = Box::new(self.learned_player());
+            let opponent: Box<dyn Player> = Box::new(SquareEvaluationPlayer::default());
+
+            let learned_index = game % 2;
+            let players = if learned_index == 0 { vec![learned, opponent] } else { vec![opponent, learned] };
+            if Game::new(players, 2).run().winner == Game::player_team(2, learned_index) {
+                wins += 1;
+            }
+        }
+
+        wins as f32 / self.options.benchmark_games as f32
+    }
+
+    fn learned_player(&self) -> LearnedPlayer {
+        LearnedPlayer { evaluator: LinearEvaluator { weights: self.evaluator.weights } }
+    }
+}
+
+// the positions (as features) reached by a player after each of its moves, shared between the player
+// and the trainer
+type Positions = Rc<RefCell<Vec<[f32; NUM_FEATURES]>>>;
+
+// player for self-play, which plays a uniformly random legal move with the given probability (so
+// that self-play explores more varied positions) and otherwise the learned player's move, and records
+// the position after each move as the learned player evaluates it, i.e. with the hand before drawing
+struct SelfPlayPlayer {
+    player: LearnedPlayer,
+    exploration: f32,
+    positions: Positions,
+}
+
+impl SelfPlayPlayer {
+    fn choose_move(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+        if thread_rng().gen::<f32>() < self.exploration {
+            if let Some(play) = legal_moves(team, hand, board).choose(&mut thread_rng()) {
+                return *play;
+            }
+        }
+
+        self.player.play(team, hand, board, deck)
+    }
+}
+
+impl Player for SelfPlayPlayer {
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+        let play = self.choose_move(team, hand, board, deck);
+
+        let (child, remaining_hand) = LearnedPlayer::after_move(team, hand, board, &play);
+        self.positions.borrow_mut().push(extract_features(&child, team, Some(&remaining_hand)));
+        play
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn training_updates_weights() {
+        let options = TrainerOptions {
+            generations: 1,
+            games_per_generation: 2,
+            epochs: 1,
+            benchmark_games: 2,
+            ..Default::default()
+        };
+        let mut trainer = Trainer::new(options, LinearEvaluator::default());
+        trainer.run();
+
+        assert_ne!(trainer.evaluator().weights, LinearEvaluator::default().weights);
+        assert_eq!(trainer.benchmarks().len(), 1);
+    }
+
+    #[test]
+    fn positions_are_recorded_for_both_teams() {
+        let trainer = Trainer::new(TrainerOptions::default(), LinearEvaluator::default());
+        let trajectories = trainer.play_game();
+
+        assert_eq!(trajectories.len(), 2);
+        assert_eq!(trajectories.iter().filter(|trajectory| trajectory.won).count(), 1);
+        assert!(trajectories.iter().all(|trajectory| !trajectory.positions.is_empty()));
+    }
+}
@@ -0,0 +1,492 @@
+use std::io;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event as TerminalEvent, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+
+use crate::core::board::{Board, BOARD_SIZE};
+use crate::core::card::Card;
+use crate::core::deck::Deck;
+use crate::core::game::Game;
+use crate::core::moves::{legal_moves, Move};
+use crate::core::player::Player;
+use crate::core::square::Square;
+use crate::core::team::Team;
+use crate::players::create_bot;
+use crate::history::Frame;
+
+mod view;
+
+// the delays between bot turns which can be chosen with + and -, and the initial choice
+const DELAYS_MS: [u64; 6] = [0, 100, 250, 500, 1000, 2000];
+const INITIAL_DELAY: usize = 3;
+
+// games in the terminal are always between two teams
+const NUM_TEAMS: usize = 2;
+
+// how often the interface checks for updates from the game while waiting for input
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// what is being shown in the terminal UI
+pub enum Mode {
+    // a game between a human (in the given seat) and bots
+    Play(usize),
+    // a game between bots only
+    Watch,
+    // a finished game which can only be stepped through
+    Replay,
+}
+
+// full-screen terminal interface for playing a game against bots, watching bots play, or replaying
+// a recorded game; every mode can step back and forward through the turns so far, and bot turns are
+// played automatically at an adjustable speed
+struct App {
+    mode: Mode,
+    frames: Vec<Frame>,
+    // the seat which plays the next turn of the game, if it is still being played
+    next_seat: Option<usize>,
+    // index of the frame being shown
+    position: usize,
+    // the decision the human is being asked to make, if any
+    prompt: Option<Prompt>,
+    cursor: Square,
+    // index in the prompt's hand of the selected card
+    selected: Option<usize>,
+    paused: bool,
+    // index in DELAYS_MS of the delay between turns
+    delay: usize,
+    // when the last turn was played (or, in a replay, shown)
+    last_step: Instant,
+    // a message for the user, e.g. why a move was rejected
+    status: String,
+    quit: bool,
+}
+
+// a decision the human player is asked to make, with the state it is made in
+struct Prompt {
+    kind: DecisionKind,
+    team: Team,
+    hand: Vec<Card>,
+    board: Board,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum DecisionKind {
+    Move,
+    DeadCard,
+}
+
+enum Decision {
+    Move(Move),
+    // the index of the dead card to exchange, if any
+    DeadCard(Option<usize>),
+}
+
+// messages from the game's thread to the interface
+enum Update {
+    // the frame for a turn which has been played, and the seat which plays next
+    Frame(Frame, usize),
+    Prompt(Prompt),
+}
+
+// the interface's side of a game being played on its own thread
+struct Connection {
+    // each message asks the game to play one more turn
+    turns: Sender<()>,
+    updates: Receiver<Update>,
+    decisions: Sender<Decision>,
+    // whether a turn has been requested and not yet played
+    playing: bool,
+}
+
+// plays a game in the terminal between the bots with the given names (see players::BOT_NAMES), with
+// the human player (if any) in the seat given by the mode
+pub fn play(mode: Mode, bots: Vec<String>) -> io::Result<()> {
+    let human_seat = match mode {
+        Mode::Play(seat) => Some(seat),
+        _ => None,
+    };
+
+    let (turns, turn_receiver) = channel();
+    let (update_sender, updates) = channel();
+    let (decisions, decision_receiver) = channel();
+    thread::spawn(move || run_game(bots, human_seat, turn_receiver, update_sender, decision_receiver));
+
+    let connection = Connection { turns, updates, decisions, playing: false };
+    run(App::new(mode, vec![]), Some(connection))
+}
+
+// steps through the given frames of a finished game in the terminal, starting paused
+pub fn replay(frames: Vec<Frame>) -> io::Result<()> {
+    let mut app = App::new(Mode::Replay, frames);
+    app.paused = true;
+    run(app, None)
+}
+
+fn run(mut app: App, mut connection: Option<Connection>) -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    let result = event_loop(&mut terminal, &mut app, &mut connection);
+
+    // restore the terminal even if the loop failed
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+fn event_loop(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    connection: &mut Option<Connection>,
+) -> io::Result<()> {
+    while !app.quit {
+        if let Some(connection) = connection {
+            while let Ok(update) = connection.updates.try_recv() {
+                if let Update::Frame(..) = update {
+                    connection.playing = false;
+                }
+                app.update(update);
+            }
+
+            if app.wants_turn() && !connection.playing {
+                connection.playing = connection.turns.send(()).is_ok();
+            }
+        } else if app.wants_turn() && app.position + 1 < app.frames.len() {
+            app.position += 1;
+            app.last_step = Instant::now();
+        }
+
+        terminal.draw(|frame| view::draw(frame, app))?;
+
+        if event::poll(POLL_INTERVAL)? {
+            if let TerminalEvent::Key(key) = event::read()? {
+                if let Some(decision) = app.handle_key(key) {
+                    if let Some(connection) = connection {
+                        let _ = connection.decisions.send(decision);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl App {
+    fn new(mode: Mode, frames: Vec<Frame>) -> App {
+        App {
+            mode,
+            frames,
+            next_seat: None,
+            position: 0,
+            prompt: None,
+            cursor: Square { row: BOARD_SIZE / 2, col: BOARD_SIZE / 2 },
+            selected: None,
+            paused: false,
+            delay: INITIAL_DELAY,
+            last_step: Instant::now(),
+            status: String::new(),
+            quit: false,
+        }
+    }
+
+    fn update(&mut self, update: Update) {
+        match update {
+            Update::Frame(frame, next_seat) => {
+                self.next_seat = Some(next_seat);
+                self.last_step = Instant::now();
+                // keep showing the latest frame, unless an earlier one is being looked at
+                if self.position + 1 >= self.frames.len() {
+                    self.position = self.frames.len();
+                }
+                self.frames.push(frame);
+            }
+            Update::Prompt(prompt) => {
+                self.status = match prompt.kind {
+                    DecisionKind::Move => String::from("Your turn: pick a card and a square"),
+                    DecisionKind::DeadCard => String::from("Pick a dead card to exchange, or k to keep it"),
+                };
+                self.position = self.frames.len().saturating_sub(1);
+                self.selected = None;
+                self.prompt = Some(prompt);
+            }
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.frames.last().map_or(false, |frame| frame.result.is_some())
+    }
+
+    // returns true if the next turn should be played (or, in a replay, shown)
+    fn wants_turn(&self) -> bool {
+        // the human's turns are requested straight away, and wait on their decision
+        let human_next = match self.mode {
+            Mode::Play(seat) => self.next_seat == Some(seat),
+            _ => false,
+        };
+
+        !self.is_over() &&
+            self.prompt.is_none() &&
+            (human_next || (!self.paused && self.last_step.elapsed() >= self.delay()))
+    }
+
+    fn delay(&self) -> Duration {
+        Duration::from_millis(DELAYS_MS[self.delay])
+    }
+
+    // the squares the selected card can be played on
+    fn targets(&self) -> Vec<Square> {
+        let (Some(prompt), Some(selected)) = (&self.prompt, self.selected) else { return vec![] };
+        if prompt.kind != DecisionKind::Move {
+            return vec![];
+        }
+
+        let card = prompt.hand[selected];
+        legal_moves(&prompt.team, &prompt.hand, &prompt.board).iter()
+            .filter(|play| play.uses(&card))
+            .map(|play| play.square())
+            .collect()
+    }
+
+    // handles a key press, returning the decision it makes, if any
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Decision> {
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('p') => self.paused = !self.paused,
+            KeyCode::Char('+') | KeyCode::Char('=') => self.delay = self.delay.saturating_sub(1),
+            KeyCode::Char('-') => self.delay = (self.delay + 1).min(DELAYS_MS.len() - 1),
+            KeyCode::Char('[') => self.position = self.position.saturating_sub(1),
+            KeyCode::Char(']') => self.position = (self.position + 1).min(self.frames.len().saturating_sub(1)),
+            KeyCode::Home => self.position = 0,
+            KeyCode::End => self.position = self.frames.len().saturating_sub(1),
+            KeyCode::Up => self.move_cursor(-1, 0),
+            KeyCode::Down => self.move_cursor(1, 0),
+            KeyCode::Left => self.move_cursor(0, -1),
+            KeyCode::Right => self.move_cursor(0, 1),
+            KeyCode::Char(digit @ '1'..='9') => {
+                let index = digit as usize - '1' as usize;
+                if self.prompt.as_ref().map_or(false, |prompt| index < prompt.hand.len()) {
+                    self.selected = Some(index);
+                }
+            }
+            KeyCode::Char('k') => return self.decide(|prompt| match prompt.kind {
+                DecisionKind::DeadCard => Ok(Decision::DeadCard(None)),
+                DecisionKind::Move => Err(String::from("Only dead cards can be kept")),
+            }),
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let (selected, cursor) = (self.selected, self.cursor);
+                return self.decide(|prompt| {
+                    let index = selected.ok_or_else(|| String::from("Pick a card first"))?;
+                    let card = prompt.hand[index];
+                    match prompt.kind {
+                        DecisionKind::Move => {
+                            let play = Move::for_card(card, cursor);
+                            play.validate(&prompt.team, &prompt.hand, &prompt.board)?;
+                            Ok(Decision::Move(play))
+                        }
+                        DecisionKind::DeadCard if prompt.board.is_dead(&card) => Ok(Decision::DeadCard(Some(index))),
+                        DecisionKind::DeadCard => Err(format!("{card} is not dead")),
+                    }
+                });
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    // makes a decision for the current prompt with the given function, showing any error instead
+    fn decide<F>(&mut self, decide: F) -> Option<Decision> where F: FnOnce(&Prompt) -> Result<Decision, String> {
+        match decide(self.prompt.as_ref()?) {
+            Ok(decision) => {
+                self.prompt = None;
+                self.selected = None;
+                self.status.clear();
+                Some(decision)
+            }
+            Err(error) => {
+                self.status = error;
+                None
+            }
+        }
+    }
+
+    fn move_cursor(&mut self, row_delta: i8, col_delta: i8) {
+        let square = self.cursor.plus(row_delta, col_delta);
+        if square.is_valid() {
+            self.cursor = square;
+        }
+    }
+}
+
+// plays the game on its own thread, one turn for each request from the interface
+fn run_game(
+    bots: Vec<String>,
+    human_seat: Option<usize>,
+    turns: Receiver<()>,
+    updates: Sender<Update>,
+    decisions: Receiver<Decision>,
+) {
+    let mut bots = bots.into_iter();
+    let num_players = bots.len() + usize::from(human_seat.is_some());
+    let mut players: Vec<Box<dyn Player>> = vec![];
+    let mut decisions = Some(decisions);
+    for seat in 0..num_players {
+        if Some(seat) == human_seat {
+            let player = HumanPlayer { updates: updates.clone(), decisions: decisions.take().unwrap() };
+            players.push(Box::new(player));
+        } else {
+            players.push(create_bot(&bots.next().unwrap()).unwrap());
+        }
+    }
+
+    let mut game = Game::new(players, NUM_TEAMS);
+    let _ = updates.send(Update::Frame(Frame::start(&game, human_seat), game.up_index()));
+
+    // stops once the interface is closed
+    while turns.recv().is_ok() {
+        let (seat, board, history) = (game.up_index(), game.board().clone(), game.deck().history().len());
+        let result = game.run_turn();
+        let frame = Frame::after_turn(&game, seat, &board, history, human_seat, result);
+        let _ = updates.send(Update::Frame(frame, game.up_index()));
+
+        if result.is_some() {
+            break;
+        }
+    }
+}
+
+// player for the human, which asks the interface for each decision
+struct HumanPlayer {
+    updates: Sender<Update>,
+    decisions: Receiver<Decision>,
+}
+
+impl HumanPlayer {
+    fn decide(&self, kind: DecisionKind, team: &Team, hand: &[Card], board: &Board) -> Option<Decision> {
+        let prompt = Prompt { kind, team: *team, hand: hand.to_vec(), board: board.clone() };
+        self.updates.send(Update::Prompt(prompt)).ok()?;
+        self.decisions.recv().ok()
+    }
+}
+
+impl Player for HumanPlayer {
+    fn play(&mut self, team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> Move {
+        match self.decide(DecisionKind::Move, team, hand, board) {
+            Some(Decision::Move(play)) => play,
+            // the interface has been closed; play anything so that the game's thread can finish
+            _ => legal_moves(team, hand, board)[0],
+        }
+    }
+
+    fn replace_dead_card(&mut self, team: &Team, hand: &[Card], board: &Board, _deck: &Deck) -> Option<usize> {
+        match self.decide(DecisionKind::DeadCard, team, hand, board) {
+            Some(Decision::DeadCard(index)) => index,
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+    use ratatui::backend::TestBackend;
+
+    use crate::core::rank::Rank;
+    use crate::core::suit::Suit;
+    use crate::players::random_player::RandomPlayer;
+
+    use super::*;
+
+    fn press(app: &mut App, code: KeyCode) -> Option<Decision> {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn render(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| view::draw(frame, app)).unwrap();
+        terminal.backend().buffer().content().iter().map(|cell| cell.symbol.as_str()).collect()
+    }
+
+    #[test]
+    fn plays_the_selected_card_on_the_cursor() {
+        let game = Game::new(vec!(Box::new(RandomPlayer::default()), Box::new(RandomPlayer::default())), 2);
+        let mut app = App::new(Mode::Play(0), vec![]);
+        app.update(Update::Frame(Frame::start(&game, Some(0)), 0));
+        assert!(app.wants_turn());
+
+        let ten = Card { rank: Rank::Ten, suit: Suit::Spades };
+        let board = game.board().clone();
+        app.update(Update::Prompt(Prompt { kind: DecisionKind::Move, team: Team::One, hand: vec![ten], board }));
+        assert!(!app.wants_turn());
+        assert!(render(&app).contains("Your turn"));
+
+        // nothing is played before a card is picked, or on a square the card does not match
+        assert!(press(&mut app, KeyCode::Enter).is_none());
+        assert_eq!(app.status, "Pick a card first");
+        press(&mut app, KeyCode::Char('1'));
+        assert_eq!(app.targets().len(), 2);
+        assert!(press(&mut app, KeyCode::Char('k')).is_none());
+
+        let target = app.targets()[0];
+        while app.cursor.row != target.row {
+            let key = if app.cursor.row < target.row { KeyCode::Down } else { KeyCode::Up };
+            press(&mut app, key);
+        }
+        while app.cursor.col != target.col {
+            let key = if app.cursor.col < target.col { KeyCode::Right } else { KeyCode::Left };
+            press(&mut app, key);
+        }
+
+        match press(&mut app, KeyCode::Enter) {
+            Some(Decision::Move(play)) => assert_eq!(play, Move::for_card(ten, target)),
+            _ => panic!("expected a move"),
+        }
+        assert!(app.prompt.is_none());
+    }
+
+    #[test]
+    fn steps_through_a_watched_game() {
+        let (turns, turn_receiver) = channel();
+        let (update_sender, updates) = channel();
+        let (_decisions, decision_receiver) = channel();
+        let bots = vec![String::from("random"), String::from("random")];
+        let game = thread::spawn(move || run_game(bots, None, turn_receiver, update_sender, decision_receiver));
+
+        let mut app = App::new(Mode::Watch, vec![]);
+        app.delay = 0;
+        app.update(updates.recv().unwrap());
+        while app.wants_turn() {
+            turns.send(()).unwrap();
+            app.update(updates.recv().unwrap());
+        }
+        game.join().unwrap();
+
+        let last = app.frames.len() - 1;
+        assert_eq!(app.position, last);
+        assert!(app.frames[last].event.ends_with("and won"));
+        assert!(render(&app).contains("won after"));
+
+        press(&mut app, KeyCode::Char('['));
+        assert_eq!(app.position, last - 1);
+        press(&mut app, KeyCode::Home);
+        assert_eq!(app.position, 0);
+        assert!(render(&app).contains("Game started"));
+        press(&mut app, KeyCode::End);
+        assert_eq!(app.position, last);
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+}
@@ -0,0 +1,165 @@
+use ratatui::backend::Backend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Span, Spans};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::core::board::BOARD_SIZE;
+use crate::core::card::Card;
+use crate::core::game::Game;
+use crate::core::square::Square;
+use crate::core::suit::Suit;
+use crate::core::team::Team;
+use crate::tui::{App, DecisionKind, Mode, DELAYS_MS, NUM_TEAMS};
+
+// the width of the board panel: a row label and BOARD_SIZE cells of 4 characters, plus borders
+const BOARD_WIDTH: u16 = 3 + 4 * BOARD_SIZE as u16 + 2;
+
+const HELP: &str = "arrows: move  1-9: pick card  enter: play  k: keep dead card  [ ]: step  \
+    home/end: first/last  p: pause  + -: speed  q: quit";
+
+// draws the whole interface: the board on the left, and the game's state, hand and log on the right
+pub fn draw<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(BOARD_WIDTH), Constraint::Min(30)])
+        .split(f.size());
+    let panels = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(7), Constraint::Length(4), Constraint::Min(3), Constraint::Length(4)])
+        .split(columns[1]);
+
+    f.render_widget(board(app), columns[0]);
+    f.render_widget(info(app), panels[0]);
+    f.render_widget(hand(app), panels[1]);
+    f.render_widget(log(app, panels[2].height.saturating_sub(2) as usize), panels[2]);
+    f.render_widget(
+        Paragraph::new(HELP).wrap(Wrap { trim: true }).block(Block::default().borders(Borders::ALL).title("Keys")),
+        panels[3],
+    );
+}
+
+fn board(app: &App) -> Paragraph<'static> {
+    let Some(frame) = app.frames.get(app.position) else {
+        return Paragraph::new("Starting...").block(Block::default().borders(Borders::ALL).title("Board"));
+    };
+
+    // the cursor and targets only matter while the human is choosing, and the latest frame is shown
+    let choosing = app.prompt.is_some() && app.position + 1 == app.frames.len();
+    let targets = if choosing { app.targets() } else { vec![] };
+
+    let mut header = vec![Span::raw("   ")];
+    header.extend((0..BOARD_SIZE).map(|col| Span::raw(format!("  {} ", (b'a' + col) as char))));
+    let mut lines = vec![Spans::from(header)];
+
+    for row in 0..BOARD_SIZE {
+        let mut spans = vec![Span::raw(format!(" {row} "))];
+        for col in 0..BOARD_SIZE {
+            let square = Square { row, col };
+            let text = frame.board.card_at(&square).map_or(String::from(" ** "), |card| format!(" {card} "));
+
+            let mut style = match frame.board.card_at(&square) {
+                Some(card) if is_red(&card) => Style::default().fg(Color::Red),
+                _ => Style::default(),
+            };
+            if let Some(team) = frame.board.chip_at(&square) {
+                style = style.bg(team_color(team)).fg(Color::Black);
+            }
+            if frame.board.in_sequence(&square) {
+                style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            }
+            if frame.square == Some(square) {
+                style = style.add_modifier(Modifier::ITALIC);
+            }
+            if targets.contains(&square) {
+                style = style.bg(Color::Yellow).fg(Color::Black);
+            }
+            if choosing && app.cursor == square {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+
+            spans.push(Span::styled(text, style));
+        }
+        lines.push(Spans::from(spans));
+    }
+
+    Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Board"))
+}
+
+fn info(app: &App) -> Paragraph<'static> {
+    let mode = match app.mode {
+        Mode::Play(seat) => format!("Playing as {} (seat {seat})", Game::player_team(NUM_TEAMS, seat)),
+        Mode::Watch => String::from("Watching bots"),
+        Mode::Replay => String::from("Replaying a recorded game"),
+    };
+    let frame = app.frames.get(app.position);
+    let turn = frame.map_or(String::new(), |frame| format!(
+        "Turn {}  (frame {} of {})", frame.turn, app.position + 1, app.frames.len(),
+    ));
+    let speed = format!(
+        "Delay {}ms{}", DELAYS_MS[app.delay], if app.paused { "  PAUSED" } else { "" },
+    );
+    let status = match app.frames.last().and_then(|frame| frame.result) {
+        Some(result) if app.status.is_empty() => format!("{} won after {} turns", result.winner, result.turns),
+        _ => app.status.clone(),
+    };
+
+    let lines = vec![
+        Spans::from(mode),
+        Spans::from(turn),
+        Spans::from(speed),
+        Spans::from(Span::styled(status, Style::default().add_modifier(Modifier::BOLD))),
+    ];
+    Paragraph::new(lines).wrap(Wrap { trim: true }).block(Block::default().borders(Borders::ALL).title("Game"))
+}
+
+fn hand(app: &App) -> Paragraph<'static> {
+    // while choosing, show the hand being chosen from, which includes any card just drawn
+    let (cards, board) = match (&app.prompt, app.frames.get(app.position)) {
+        (Some(prompt), _) => (prompt.hand.clone(), Some(&prompt.board)),
+        (None, Some(frame)) => (frame.hand.clone(), Some(&frame.board)),
+        (None, None) => (vec![], None),
+    };
+    let dead_prompt = app.prompt.as_ref().map_or(false, |prompt| prompt.kind == DecisionKind::DeadCard);
+
+    let mut spans = vec![];
+    for (i, card) in cards.iter().enumerate() {
+        let mut style = if is_red(card) { Style::default().fg(Color::Red) } else { Style::default() };
+        if app.selected == Some(i) {
+            style = style.bg(Color::Yellow).fg(Color::Black);
+        }
+        if board.map_or(false, |board| board.is_dead(card)) {
+            style = style.add_modifier(Modifier::CROSSED_OUT);
+        }
+        spans.push(Span::raw(format!("{}:", i + 1)));
+        spans.push(Span::styled(card.to_string(), style));
+        spans.push(Span::raw("  "));
+    }
+
+    let title = if dead_prompt { "Hand (dead card)" } else { "Hand" };
+    Paragraph::new(Spans::from(spans))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title(title))
+}
+
+// the events of the last few frames up to the one being shown, most recent last
+fn log(app: &App, height: usize) -> Paragraph<'static> {
+    let end = (app.position + 1).min(app.frames.len());
+    let lines: Vec<Spans> = app.frames[end.saturating_sub(height)..end].iter()
+        .map(|frame| Spans::from(frame.event.clone()))
+        .collect();
+    Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Log"))
+}
+
+fn team_color(team: Team) -> Color {
+    match team {
+        Team::One => Color::Blue,
+        Team::Two => Color::Green,
+        Team::Three => Color::Red,
+    }
+}
+
+fn is_red(card: &Card) -> bool {
+    card.suit == Suit::Hearts || card.suit == Suit::Diamonds
+}
@@ -0,0 +1,294 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rand::Rng;
+use rand::thread_rng;
+
+use crate::core::game::Game;
+use crate::core::player::Player;
+use crate::log::LogLevel;
+use crate::util::params::Parameterized;
+
+// inclusive range of values which the tuner may assign to a single named parameter
+pub struct ParamRange {
+    pub name: &'static str,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ParamRange {
+    fn normalize(&self, value: f32) -> f32 {
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+
+    fn denormalize(&self, value: f32) -> f32 {
+        self.min + value.clamp(0.0, 1.0) * (self.max - self.min)
+    }
+}
+
+// the opponent against which candidate parameter sets are evaluated
+pub enum TuningOpponent<P> {
+    // a fixed player, created fresh for each game
+    Baseline(Box<dyn Fn() -> Box<dyn Player>>),
+
+    // the best parameter set found so far (starting with the initial parameters), which is replaced
+    // by any candidate that beats it outright
+    PreviousBest(fn(P) -> Box<dyn Player>),
+}
+
+pub struct TunerOptions {
+    pub iterations: usize,
+
+    // number of games played to evaluate each candidate; seats alternate between games so this
+    // should be even
+    pub games_per_evaluation: usize,
+
+    // number of games played to evaluate the final parameters, which is larger than
+    // games_per_evaluation since that win rate is the one reported
+    pub final_games: usize,
+
+    // SPSA gain sequences, in normalized parameter space (where each parameter ranges over [0, 1]):
+    // at iteration k the step size is a / (k + 1 + stability)^alpha and the perturbation size is
+    // c / (k + 1)^gamma
+    pub a: f32,
+    pub c: f32,
+    pub alpha: f32,
+    pub gamma: f32,
+    pub stability: f32,
+}
+
+impl Default for TunerOptions {
+    fn default() -> Self {
+        TunerOptions {
+            iterations: 100,
+            games_per_evaluation: 50,
+            final_games: 400,
+            a: 0.5,
+            c: 0.1,
+            alpha: 0.602,
+            gamma: 0.101,
+            stability: 10.0,
+        }
+    }
+}
+
+// a single evaluated parameter set in the search
+pub struct TuningStep {
+    pub iteration: usize,
+    pub params: String,
+    pub win_rate: f32,
+}
+
+// searches the space of parameters for a player via simultaneous perturbation stochastic
+// approximation (SPSA): at each iteration every parameter is perturbed at once in a random
+// direction, both the positive and negative perturbations play a batch of games against the
+// opponent, and the difference in their win rates is used to estimate the gradient; the result is
+// the final iterate, rather than whichever candidate was luckiest in its small batch of games
+pub struct Tuner<P> where P: Parameterized + Player + Clone + 'static {
+    space: Vec<ParamRange>,
+    opponent: TuningOpponent<P>,
+    options: TunerOptions,
+
+    // the parameters the search starts from, which every candidate is based on so that parameters
+    // outside of the space keep their initial values
+    initial: P,
+
+    best: P,
+    // the win rate of the best parameters against the opponent, measured over final_games games
+    best_win_rate: f32,
+    // the parameters played against with TuningOpponent::PreviousBest
+    previous_best: P,
+    log: Vec<TuningStep>,
+}
+
+impl<P> Tuner<P> where P: Parameterized + Player + Clone + 'static {
+    pub fn new(space: Vec<ParamRange>, opponent: TuningOpponent<P>, options: TunerOptions, initial: P) -> Tuner<P> {
+        debug_assert!(
+            space.iter().all(|range| P::param_names().contains(&range.name) && range.min < range.max),
+            "invalid parameter space",
+        );
+
+        Tuner {
+            space,
+            opponent,
+            options,
+            best: initial.clone(),
+            best_win_rate: 0.0,
+            previous_best: initial.clone(),
+            initial,
+            log: vec![],
+        }
+    }
+
+    // runs the search starting from the initial parameters, returning the parameters it converges on
+    pub fn run(&mut self) -> &P {
+        let mut theta: Vec<f32> = self.space.iter()
+            .map(|range| range.normalize(self.initial.param(range.name).unwrap()))
+            .collect();
+
+        let win_rate = self.evaluate(&theta, self.options.games_per_evaluation);
+        self.record(0, &theta, win_rate);
+
+        for k in 0..self.options.iterations {
+            let step = self.options.a /
+                (k as f32 + 1.0 + self.options.stability).powf(self.options.alpha);
+            let perturbation = self.options.c / (k as f32 + 1.0).powf(self.options.gamma);
+
+            let delta: Vec<f32> = self.space.iter()
+                .map(|_| if thread_rng().gen::<bool>() { 1.0 } else { -1.0 })
+                .collect();
+
+            let plus: Vec<f32> = theta.iter().zip(&delta)
+                .map(|(t, d)| (t + perturbation * d).clamp(0.0, 1.0))
+                .collect();
+            let minus: Vec<f32> = theta.iter().zip(&delta)
+                .map(|(t, d)| (t - perturbation * d).clamp(0.0, 1.0))
+                .collect();
+
+            let win_rate_plus = self.evaluate(&plus, self.options.games_per_evaluation);
+            let win_rate_minus = self.evaluate(&minus, self.options.games_per_evaluation);
+
+            // ascend the estimated gradient, since we are maximizing the win rate
+            for (t, d) in theta.iter_mut().zip(&delta) {
+                let gradient = (win_rate_plus - win_rate_minus) / (2.0 * perturbation * d);
+                *t = (*t + step * gradient).clamp(0.0, 1.0);
+            }
+
+            for (candidate, win_rate) in [(&plus, win_rate_plus), (&minus, win_rate_minus)] {
+                self.record(k + 1, candidate, win_rate);
+                if matches!(self.opponent, TuningOpponent::PreviousBest(_)) && win_rate > 0.5 {
+                    self.previous_best = self.create(candidate);
+                }
+            }
+
+            LogLevel::Results.log(
+                &format!("Tuning iteration {}/{}: win rates {:.3} and {:.3}, now at {}",
+                         k + 1, self.options.iterations, win_rate_plus, win_rate_minus,
+                         self.create(&theta).to_param_string()),
+            );
+        }
+
+        self.best = self.create(&theta);
+        self.best_win_rate = self.evaluate(&theta, self.options.final_games);
+        self.record(self.options.iterations + 1, &theta, self.best_win_rate);
+        &self.best
+    }
+
+    pub fn best(&self) -> &P {
+        &self.best
+    }
+
+    pub fn best_win_rate(&self) -> f32 {
+        self.best_win_rate
+    }
+
+    pub fn log(&self) -> &[TuningStep] {
+        &self.log
+    }
+
+    // writes the best parameter set found to the given file, one parameter per line, in a format
+    // readable by Parameterized::from_param_file()
+    pub fn write_best<Q: AsRef<Path>>(&self, path: Q) -> io::Result<()> {
+        let contents: String = self.best.params().iter()
+            .map(|(name, value)| format!("{name}={value}\n"))
+            .collect();
+        fs::write(path, format!("# win rate: {}\n{}", self.best_win_rate, contents))
+    }
+
+    // writes the log of every evaluated parameter set to the given file, as tab-separated values
+    pub fn write_log<Q: AsRef<Path>>(&self, path: Q) -> io::Result<()> {
+        let mut contents = String::from("iteration\twin_rate\tparams\n");
+        for step in &self.log {
+            contents.push_str(&format!("{}\t{}\t{}\n", step.iteration, step.win_rate, step.params));
+        }
+        fs::write(path, contents)
+    }
+
+    fn record(&mut self, iteration: usize, theta: &[f32], win_rate: f32) {
+        let params = self.create(theta).to_param_string();
+        self.log.push(TuningStep { iteration, params, win_rate });
+    }
+
+    // creates a player with the given normalized parameters, and the initial values of the rest
+    fn create(&self, theta: &[f32]) -> P {
+        let mut player = self.initial.clone();
+        for (range, value) in self.space.iter().zip(theta) {
+            player.set_param(range.name, range.denormalize(*value)).unwrap();
+        }
+        player
+    }
+
+    // returns the fraction of the given number of games won by a player with the given normalized
+    // parameters against the opponent
+    fn evaluate(&self, theta: &[f32], num_games: usize) -> f32 {
+        let mut wins = 0;
+        for game in 0..num_games {
+            let candidate: Box<dyn Player> = Box::new(self.create(theta));
+            let opponent: Box<dyn Player> = match &self.opponent {
+                TuningOpponent::Baseline(create) => create(),
+                TuningOpponent::PreviousBest(create) => create(self.previous_best.clone()),
+            };
+
+            // alternate which seat the candidate takes to avoid first-player advantage
+            let candidate_index = game % 2;
+            let players = if candidate_index == 0 {
+                vec![candidate, opponent]
+            } else {
+                vec![opponent, candidate]
+            };
+
+            let result = Game::new(players, 2).run();
+            if result.winner == Game::player_team(2, candidate_index) {
+                wins += 1;
+            }
+        }
+
+        wins as f32 / num_games as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::players::random_player::RandomPlayer;
+    use crate::players::square_evaluation_player::SquareEvaluationPlayer;
+
+    use super::*;
+
+    #[test]
+    fn param_range_normalization() {
+        let range = ParamRange { name: "a", min: 2.0, max: 6.0 };
+        assert_eq!(range.normalize(3.0), 0.25);
+        assert_eq!(range.denormalize(0.25), 3.0);
+        assert_eq!(range.normalize(10.0), 1.0);
+        assert_eq!(range.denormalize(-1.0), 2.0);
+    }
+
+    #[test]
+    fn tuner_stays_within_parameter_space() {
+        let space = vec![
+            ParamRange { name: "owned_square_value", min: 5.0, max: 15.0 },
+            ParamRange { name: "distance_falloff", min: 0.0, max: 0.2 },
+        ];
+        let options = TunerOptions { iterations: 2, games_per_evaluation: 2, final_games: 4, ..Default::default() };
+        let initial = SquareEvaluationPlayer { reachable_square_value: 4.0, ..Default::default() };
+        let mut tuner: Tuner<SquareEvaluationPlayer> = Tuner::new(
+            space,
+            TuningOpponent::Baseline(Box::new(|| Box::new(RandomPlayer::default()))),
+            options,
+            initial,
+        );
+
+        let best = tuner.run();
+        assert!((5.0..=15.0).contains(&best.owned_square_value));
+        assert!((0.0..=0.2).contains(&best.distance_falloff));
+        // parameters outside of the space keep their initial values
+        assert_eq!(best.reachable_square_value, 4.0);
+        let params = best.to_param_string();
+
+        // initial evaluation, two candidates per iteration and the final evaluation
+        assert_eq!(tuner.log().len(), 6);
+        assert_eq!(tuner.log().last().unwrap().params, params);
+        assert!([0.0, 0.25, 0.5, 0.75, 1.0].contains(&tuner.best_win_rate()));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod params;
 pub mod wrapper;
 
 pub fn generate_vector<T, F>(count: usize, f: F) -> Vec<T> where F: FnMut(usize) -> T {
@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::Path;
+
+// trait for types (typically players) which are configured by a set of named numeric parameters,
+// e.g. heuristic weights; this allows them to be constructed from a parameter string such as
+// "a=1.0,b=2.5" or a file with one "name=value" pair per line (blank lines and lines starting with
+// '#' are ignored)
+pub trait Parameterized: Default {
+    // names of all the parameters, in a stable order
+    fn param_names() -> &'static [&'static str];
+
+    // returns the current value of the parameter with the given name, or None if there is no such
+    // parameter
+    fn param(&self, name: &str) -> Option<f32>;
+
+    // sets the parameter with the given name; returns an error if there is no such parameter or the
+    // value is invalid for it
+    fn set_param(&mut self, name: &str, value: f32) -> Result<(), String>;
+
+    // returns the current values of all the parameters, in the order of param_names()
+    fn params(&self) -> Vec<(&'static str, f32)> {
+        Self::param_names().iter()
+            .map(|name| (*name, self.param(name).unwrap()))
+            .collect()
+    }
+
+    // sets each of the parameters in the given string, separated by commas or newlines; parameters
+    // which are not specified are left unchanged
+    fn apply_param_string(&mut self, string: &str) -> Result<(), String> {
+        for (name, value) in parse_param_string(string)? {
+            self.set_param(&name, value)?;
+        }
+        Ok(())
+    }
+
+    // creates an instance from the default values, overridden by those in the given string
+    fn from_param_string(string: &str) -> Result<Self, String> {
+        let mut result = Self::default();
+        result.apply_param_string(string)?;
+        Ok(result)
+    }
+
+    // creates an instance from the default values, overridden by those in the given file
+    fn from_param_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let contents = fs::read_to_string(path.as_ref())
+            .map_err(|err| format!("unable to read {}: {}", path.as_ref().display(), err))?;
+        Self::from_param_string(&contents)
+    }
+
+    // formats all the parameters as a string which can be parsed by from_param_string()
+    fn to_param_string(&self) -> String {
+        self.params().iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+}
+
+// parses a string of "name=value" pairs separated by commas or newlines
+pub fn parse_param_string(string: &str) -> Result<Vec<(String, f32)>, String> {
+    string.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.starts_with('#'))
+        .flat_map(|line| line.split(','))
+        .map(|pair| pair.trim())
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=')
+                .ok_or_else(|| format!("invalid parameter (expected name=value): {pair}"))?;
+            let value = value.trim().parse::<f32>()
+                .map_err(|err| format!("invalid value for parameter {}: {}", name.trim(), err))?;
+            // NaN would also pass any range check, since comparisons with it are false
+            if !value.is_finite() {
+                return Err(format!("invalid value for parameter {}: {} is not finite", name.trim(), value));
+            }
+            Ok((String::from(name.trim()), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_comma_and_newline_separated_params() {
+        let params = parse_param_string("# comment\na=1.5, b=2\n\nc = -0.25,\n").unwrap();
+        assert_eq!(
+            params,
+            vec![(String::from("a"), 1.5), (String::from("b"), 2.0), (String::from("c"), -0.25)],
+        );
+    }
+
+    #[test]
+    fn parse_invalid_params() {
+        assert!(parse_param_string("a").is_err());
+        assert!(parse_param_string("a=b").is_err());
+        assert!(parse_param_string("a=NaN").is_err());
+        assert!(parse_param_string("a=inf").is_err());
+        assert!(parse_param_string("a=-inf").is_err());
+    }
+}
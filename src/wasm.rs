@@ -0,0 +1,276 @@
+// a wrapper around a game for JavaScript when compiled to WebAssembly (e.g. with
+// `wasm-pack build --target web`), so that a static page can host games against bots which run in
+// the browser
+//
+// seats are played by bots (see players::BOT_NAMES) or by "human"s whose decisions are submitted
+// from JavaScript; states are returned as JSON strings in the same format as the HTTP API in web.rs,
+// and errors are thrown as exceptions
+//
+// since a turn cannot wait for JavaScript, a human's dead card exchange is made as soon as it is
+// decided (see Game::exchange_dead_cards()), and their move is then chosen from the hand with the
+// replacement card
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::Deck;
+use crate::core::game::{Game, GameResult};
+use crate::core::moves::{legal_moves, Move};
+use crate::core::player::Player;
+use crate::core::team::Team;
+use crate::players::create_bot;
+use crate::server::{cards_json, move_json, public_state_json};
+
+const HUMAN: &str = "human";
+
+#[wasm_bindgen]
+pub struct WasmGame {
+    game: Game,
+    players: Vec<String>,
+    // the decisions of each human player, shared with the player in the game
+    decisions: Vec<Option<Rc<RefCell<Decisions>>>>,
+    result: Option<GameResult>,
+}
+
+// the decisions a human has submitted for their next turn
+#[derive(Default)]
+struct Decisions {
+    // whether the human has decided on their dead card exchange, and the card to exchange until the
+    // game has exchanged it
+    exchanged: bool,
+    dead_card: Option<Card>,
+    play: Option<Move>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Awaiting {
+    DeadCard,
+    Move,
+}
+
+#[wasm_bindgen]
+impl WasmGame {
+    // creates a game between the given players ("human" or the name of a bot) in the given number of
+    // teams; its deals are determined by the seed if one is given
+    #[wasm_bindgen(constructor)]
+    pub fn new(players: Vec<String>, num_teams: usize, seed: Option<u32>) -> Result<WasmGame, JsError> {
+        if !Game::is_supported(players.len(), num_teams) {
+            return Err(JsError::new(&format!("unsupported game of {} players in {num_teams} teams", players.len())));
+        }
+
+        let mut decisions = vec![];
+        let mut game_players: Vec<Box<dyn Player>> = vec![];
+        for player in &players {
+            if player == HUMAN {
+                let shared = Rc::new(RefCell::new(Decisions::default()));
+                game_players.push(Box::new(JsPlayer { decisions: Rc::clone(&shared) }));
+                decisions.push(Some(shared));
+            } else {
+                game_players.push(create_bot(player).ok_or_else(|| JsError::new(&format!("unknown bot: {player}")))?);
+                decisions.push(None);
+            }
+        }
+
+        let game = match seed {
+            Some(seed) => Game::with_seed(game_players, num_teams, seed as u64),
+            None => Game::new(game_players, num_teams),
+        };
+        Ok(WasmGame { game, players, decisions, result: None })
+    }
+
+    // returns the public state of the game: players, cards, chips, sequences, draw_pile,
+    // discard_pile, turns, up (the seat to play next), awaiting (the seat and decision the game is
+    // waiting on from a human, if any) and result
+    pub fn state(&self) -> String {
+        let board = self.game.board();
+        let players: Vec<Value> = self.players.iter()
+            .enumerate()
+            .map(|(seat, player)| json!({ "seat": seat, "team": self.team(seat).to_string(), "player": player }))
+            .collect();
+
+        let mut state = public_state_json(board, self.game.deck());
+        state["players"] = json!(players);
+        state["cards"] = cards_json(board);
+        state["turns"] = json!(self.game.turn_count());
+        state["up"] = json!(self.game.up_index());
+        state["awaiting"] = match self.awaiting() {
+            Some(awaiting) => json!({
+                "seat": self.game.up_index(),
+                "decision": if awaiting == Awaiting::Move { "move" } else { "dead_card" },
+            }),
+            None => Value::Null,
+        };
+        state["result"] = match self.result {
+            Some(result) => json!({ "winner": result.winner.to_string(), "turns": result.turns }),
+            None => Value::Null,
+        };
+        state.to_string()
+    }
+
+    // returns the hand of the human in the given seat, with its legal_moves and dead_cards
+    pub fn hand(&self, seat: usize) -> Result<String, JsError> {
+        let hand = self.human_hand(seat)?;
+        let board = self.game.board();
+
+        let moves: Vec<Value> = legal_moves(&self.team(seat), &hand, board).iter()
+            .filter_map(|play| play.card_index(&hand).map(|index| move_json(&hand[index], play)))
+            .collect();
+        let dead_cards: Vec<String> = hand.iter()
+            .filter(|card| board.is_dead(card))
+            .map(|card| card.to_string())
+            .collect();
+
+        Ok(json!({
+            "seat": seat,
+            "team": self.team(seat).to_string(),
+            "hand": hand.iter().map(|card| card.to_string()).collect::<Vec<String>>(),
+            "legal_moves": moves,
+            "dead_cards": dead_cards,
+        }).to_string())
+    }
+
+    // decides which dead card the human who is up exchanges this turn, or null to keep them all
+    pub fn exchange_dead_card(&mut self, card: Option<String>) -> Result<(), JsError> {
+        let seat = self.awaited_seat(Awaiting::DeadCard)?;
+        let card = match card {
+            Some(card) => {
+                let card: Card = card.parse().map_err(|error: String| JsError::new(&error))?;
+                if !self.game.hand(seat).contains(&card) || !self.game.board().is_dead(&card) {
+                    return Err(JsError::new(&format!("{card} is not a dead card in the hand")));
+                }
+                Some(card)
+            }
+            None => None,
+        };
+
+        let mut decisions = self.human_decisions(seat).borrow_mut();
+        decisions.exchanged = true;
+        decisions.dead_card = card;
+        drop(decisions);
+
+        self.game.exchange_dead_cards();
+        Ok(())
+    }
+
+    // plays the given card on the given square for the human who is up, taking their turn
+    pub fn submit_move(&mut self, card: String, square: String) -> Result<(), JsError> {
+        let seat = self.awaited_seat(Awaiting::Move)?;
+        let card: Card = card.parse().map_err(|error: String| JsError::new(&error))?;
+        let square = square.parse().map_err(|error: String| JsError::new(&error))?;
+
+        let play = Move::for_card(card, square);
+        play.validate(&self.team(seat), &self.human_hand(seat)?, self.game.board())
+            .map_err(|error| JsError::new(&error))?;
+
+        self.human_decisions(seat).borrow_mut().play = Some(play);
+        self.run_turn();
+        Ok(())
+    }
+
+    // takes the turn of the bot who is up (or skips the turn of a human with no playable cards)
+    pub fn play_turn(&mut self) -> Result<(), JsError> {
+        if self.result.is_some() {
+            return Err(JsError::new("the game is over"));
+        }
+        if self.awaiting().is_some() {
+            return Err(JsError::new(&format!("the game is waiting on seat {}", self.game.up_index())));
+        }
+
+        self.run_turn();
+        Ok(())
+    }
+
+    // returns the move the given bot would make for the human in the given seat, if they are up
+    pub fn suggest_move(&self, seat: usize, bot: String) -> Result<String, JsError> {
+        let hand = self.human_hand(seat)?;
+        if self.awaiting() != Some(Awaiting::Move) || self.game.up_index() != seat {
+            return Err(JsError::new(&format!("seat {seat} is not choosing a move")));
+        }
+        let mut player = create_bot(&bot).ok_or_else(|| JsError::new(&format!("unknown bot: {bot}")))?;
+
+        let (team, board) = (self.team(seat), self.game.board());
+        player.on_game_start(seat, &team, &self.game.config());
+        let play = player.play(&team, &hand, board, &self.game.deck().public_copy());
+
+        let index = play.validate(&team, &hand, board).map_err(|error| JsError::new(&error))?;
+        Ok(move_json(&hand[index], &play).to_string())
+    }
+}
+
+impl WasmGame {
+    fn team(&self, seat: usize) -> Team {
+        Game::player_team(self.game.config().num_teams, seat)
+    }
+
+    fn human_decisions(&self, seat: usize) -> &Rc<RefCell<Decisions>> {
+        self.decisions[seat].as_ref().unwrap()
+    }
+
+    fn human_hand(&self, seat: usize) -> Result<Vec<Card>, JsError> {
+        match self.decisions.get(seat) {
+            Some(Some(_)) => Ok(self.game.hand(seat).to_vec()),
+            Some(None) => Err(JsError::new(&format!("seat {seat} is not played by a human"))),
+            None => Err(JsError::new(&format!("no such seat: {seat}"))),
+        }
+    }
+
+    // the decision the game is waiting on from the human who is up, if any
+    fn awaiting(&self) -> Option<Awaiting> {
+        let seat = self.game.up_index();
+        if self.result.is_some() {
+            return None;
+        }
+        let decisions = self.decisions[seat].as_ref()?.borrow();
+
+        let (board, team) = (self.game.board(), self.team(seat));
+        let hand = self.game.hand(seat);
+        if !decisions.exchanged && hand.iter().any(|card| board.is_dead(card)) {
+            return Some(Awaiting::DeadCard);
+        }
+
+        if hand.iter().any(|card| board.can_be_played(card, &team)) {
+            Some(Awaiting::Move)
+        } else {
+            // the turn is skipped
+            None
+        }
+    }
+
+    fn awaited_seat(&self, awaiting: Awaiting) -> Result<usize, JsError> {
+        match self.awaiting() {
+            Some(current) if current == awaiting => Ok(self.game.up_index()),
+            _ if self.result.is_some() => Err(JsError::new("the game is over")),
+            _ => Err(JsError::new("the game is not waiting on this decision")),
+        }
+    }
+
+    fn run_turn(&mut self) {
+        let seat = self.game.up_index();
+        self.result = self.game.run_turn();
+
+        if let Some(decisions) = &self.decisions[seat] {
+            *decisions.borrow_mut() = Decisions::default();
+        }
+    }
+}
+
+// a human player, whose decisions have already been submitted from JavaScript by the time the game
+// asks for them
+struct JsPlayer {
+    decisions: Rc<RefCell<Decisions>>,
+}
+
+impl Player for JsPlayer {
+    fn play(&mut self, _team: &Team, _hand: &[Card], _board: &Board, _deck: &Deck) -> Move {
+        self.decisions.borrow_mut().play.take().expect("the move is submitted before the turn is run")
+    }
+
+    fn replace_dead_card(&mut self, _team: &Team, hand: &[Card], _board: &Board, _deck: &Deck) -> Option<usize> {
+        let card = self.decisions.borrow_mut().dead_card.take()?;
+        hand.iter().position(|hand_card| *hand_card == card)
+    }
+}
@@ -0,0 +1,696 @@
+use std::collections::HashMap;
+use std::io;
+use std::io::Cursor;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::core::board::Board;
+use crate::core::card::Card;
+use crate::core::deck::Deck;
+use crate::core::game::{Game, GameConfig, GameResult};
+use crate::core::moves::{legal_moves, Move};
+use crate::core::player::Player;
+use crate::core::team::Team;
+use crate::players::{BOT_NAMES, create_bot};
+use crate::server::{cards_json, move_json, public_state_json};
+
+// the page served at /, which plays a game against SquareEvaluationPlayer using the API below
+const INDEX_HTML: &str = include_str!("../web/index.html");
+
+// the kind of player for a seat in which moves are submitted through the API
+const HUMAN: &str = "human";
+
+// how long a request which submits a decision waits for the game to need the next one
+const DECISION_TIMEOUT: Duration = Duration::from_secs(10);
+
+// number of threads handling requests, so that a request waiting on a game does not hold up others
+const WORKERS: usize = 8;
+
+// how long a game is kept after it was last requested, whether it is over or was abandoned
+const GAME_EXPIRY: Duration = Duration::from_secs(30 * 60);
+
+// an HTTP server exposing a JSON API for playing games, e.g. from a browser; every response is a
+// JSON object, with an "error" message for any status other than 200:
+// - GET /: a page which plays a game in the browser
+// - GET /bots: the bots which can be chosen for a seat
+// - POST /games with {"players":[...],"teams":...}: creates a game with a player for each seat,
+//   either "human" or one of the bots, and 2 teams by default (3 for 3 or 9 players); returns the
+//   game as for GET /games/<id>
+// - GET /games/<id>: the public state of the game: id, players, cards (as in the start message of
+//   server.rs), chips, sequences, draw_pile, discard_pile, turns, awaiting (the seat and decision,
+//   "move" or "dead_card", which the game is waiting on, if any), result (winner and turns, once
+//   the game is over) and error (the reason the game stopped early if a bot failed, otherwise null)
+// - GET /games/<id>/hands/<seat>: the hand of a human seat, with its legal_moves and dead_cards
+// - POST /games/<id>/moves with {"seat":...,"card":...,"square":...}: makes a move for a human seat;
+//   returns the game once it is waiting on a human decision again (or is over)
+// - POST /games/<id>/exchanges with {"seat":...,"card":...}: exchanges a dead card for a human seat,
+//   or keeps the hand as is if card is null; returns the game as for moves
+// - GET /games/<id>/suggestions/<seat>?bot=<bot>: the move the given bot (square_evaluation by
+//   default) would make for a human seat
+// games which have not been requested for GAME_EXPIRY are removed, whether they are over or abandoned
+// note that the API is meant for local use only: anyone who can reach it can see and play any seat
+pub struct WebServer {
+    server: tiny_http::Server,
+    games: Mutex<Games>,
+    // how long a game is kept after it was last requested
+    game_expiry: Duration,
+}
+
+struct Games {
+    // games by id; ids are not reused once games are removed
+    games: HashMap<usize, Arc<WebGame>>,
+    next_id: usize,
+}
+
+// a game being played on its own thread, from the point of view of the API
+struct WebGame {
+    players: Vec<String>,
+    config: GameConfig,
+    shared: Arc<Shared>,
+    // the channel to the player for each human seat
+    decisions: Mutex<Vec<Option<Sender<Decision>>>>,
+    last_requested: Mutex<Instant>,
+}
+
+// the state of a game, published by its thread after each turn and whenever it waits on a human
+struct Shared {
+    state: Mutex<GameState>,
+    changed: Condvar,
+}
+
+struct GameState {
+    board: Board,
+    // only the public state of the deck
+    deck: Deck,
+    // the hand of each human seat, empty for bots
+    hands: Vec<Vec<Card>>,
+    turns: usize,
+    awaiting: Option<(usize, DecisionKind)>,
+    result: Option<GameResult>,
+    // the reason the game's thread stopped before the game was over, i.e. the message of a panic
+    error: Option<String>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum DecisionKind {
+    Move,
+    DeadCard,
+}
+
+enum Decision {
+    Move(Move),
+    // the index of the dead card to exchange, if any
+    DeadCard(Option<usize>),
+}
+
+// an error response: the HTTP status and message
+type ApiError = (u16, String);
+
+impl WebServer {
+    pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<WebServer> {
+        let server = tiny_http::Server::http(address)
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        let games = Games { games: HashMap::new(), next_id: 0 };
+        Ok(WebServer { server, games: Mutex::new(games), game_expiry: GAME_EXPIRY })
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    // serves requests on WORKERS threads until the server fails
+    pub fn run(self) -> io::Result<()> {
+        let server = Arc::new(self);
+        let workers: Vec<_> = (0..WORKERS)
+            .map(|_| {
+                let server = Arc::clone(&server);
+                thread::spawn(move || server.serve())
+            })
+            .collect();
+
+        for worker in workers {
+            worker.join().unwrap_or_else(|_| Err(io::Error::new(io::ErrorKind::Other, "web worker panicked")))?;
+        }
+        Ok(())
+    }
+
+    // serves requests on the current thread until the server fails; failing to respond to a single
+    // request (e.g. because the client disconnected) does not stop the server
+    fn serve(&self) -> io::Result<()> {
+        loop {
+            let mut request = self.server.recv()?;
+            if *request.method() == Method::Get && request.url() == "/" {
+                let _ = respond(request, 200, "text/html; charset=utf-8", INDEX_HTML.to_string());
+                continue;
+            }
+
+            let mut body = String::new();
+            let response = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => self.handle(&request, &body),
+                Err(error) => Err((400, error.to_string())),
+            };
+
+            let (status, json) = match response {
+                Ok(json) => (200, json),
+                Err((status, message)) => (status, json!({ "error": message })),
+            };
+            let _ = respond(request, status, "application/json", json.to_string());
+        }
+    }
+
+    fn handle(&self, request: &Request, body: &str) -> Result<Value, ApiError> {
+        let (path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+        match (request.method(), &segments[..]) {
+            (Method::Get, ["bots"]) => Ok(json!(BOT_NAMES)),
+            (Method::Post, ["games"]) => self.create_game(&parse_body(body)?),
+            (Method::Get, ["games", id]) => Ok(self.game(id)?.to_json(parse_number(id)?)),
+            (Method::Get, ["games", id, "hands", seat]) => self.game(id)?.hand(parse_number(seat)?),
+            (Method::Post, ["games", id, "moves"]) => {
+                let body = parse_body(body)?;
+                let game = self.game(id)?;
+                game.submit_move(&body)?;
+                Ok(game.to_json(parse_number(id)?))
+            }
+            (Method::Post, ["games", id, "exchanges"]) => {
+                let body = parse_body(body)?;
+                let game = self.game(id)?;
+                game.submit_exchange(&body)?;
+                Ok(game.to_json(parse_number(id)?))
+            }
+            (Method::Get, ["games", id, "suggestions", seat]) => {
+                let bot = query.split('&')
+                    .find_map(|parameter| parameter.strip_prefix("bot="))
+                    .unwrap_or(BOT_NAMES[0]);
+                self.game(id)?.suggest(parse_number(seat)?, bot)
+            }
+            _ => Err((404, format!("no such endpoint: {} {}", request.method(), path))),
+        }
+    }
+
+    fn create_game(&self, body: &Value) -> Result<Value, ApiError> {
+        let players: Vec<String> = body["players"].as_array()
+            .ok_or_else(|| (400, String::from("expected a list of players")))?
+            .iter()
+            .map(|player| match player.as_str() {
+                Some(player) if player == HUMAN || BOT_NAMES.contains(&player) => Ok(player.to_string()),
+                _ => Err((400, format!("unknown player: {player}"))),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let num_players = players.len();
+        let default_teams = if num_players % 2 == 0 { 2 } else { 3 };
+        let num_teams = body["teams"].as_u64().map_or(default_teams, |teams| teams as usize);
+        if !Game::is_supported(num_players, num_teams) {
+            return Err((400, format!("unsupported game: {num_players} players in {num_teams} teams")));
+        }
+
+        // start the game (which plays until it needs a human decision) without holding up other requests
+        let game = Arc::new(WebGame::start(players, num_teams, create_bot));
+
+        let id = {
+            let mut games = self.games();
+            let id = games.next_id;
+            games.next_id += 1;
+            games.games.insert(id, Arc::clone(&game));
+            id
+        };
+        Ok(game.to_json(id))
+    }
+
+    fn game(&self, id: &str) -> Result<Arc<WebGame>, ApiError> {
+        let id = parse_number(id)?;
+        let game = self.games().games.get(&id)
+            .cloned()
+            .ok_or_else(|| (404, format!("no such game: {id}")))?;
+
+        *game.last_requested.lock().unwrap() = Instant::now();
+        Ok(game)
+    }
+
+    // returns the games, after removing those which have expired
+    fn games(&self) -> MutexGuard<'_, Games> {
+        let mut games = self.games.lock().unwrap();
+        games.games.retain(|_, game| game.last_requested.lock().unwrap().elapsed() < self.game_expiry);
+        games
+    }
+}
+
+impl WebGame {
+    // starts a game between the given players (with bots created by the given function) on a new
+    // thread, and waits until the game needs a decision from a human
+    fn start(players: Vec<String>, num_teams: usize, create_bot: fn(&str) -> Option<Box<dyn Player>>) -> WebGame {
+        let num_players = players.len();
+        let state = GameState {
+            board: Board::standard_board(),
+            deck: Deck::new().public_copy(),
+            hands: vec![vec![]; num_players],
+            turns: 0,
+            awaiting: None,
+            result: None,
+            error: None,
+        };
+        let shared = Arc::new(Shared { state: Mutex::new(state), changed: Condvar::new() });
+
+        // players are created on the game's thread, since they need not be Send
+        let mut decisions = vec![];
+        let mut receivers = vec![];
+        for player in &players {
+            let (sender, receiver) = if player == HUMAN {
+                let (sender, receiver) = channel();
+                (Some(sender), Some(receiver))
+            } else {
+                (None, None)
+            };
+            decisions.push(sender);
+            receivers.push(receiver);
+        }
+
+        let thread_players = players.clone();
+        let thread_shared = Arc::clone(&shared);
+        thread::spawn(move || {
+            let played = panic::catch_unwind(AssertUnwindSafe(|| {
+                let players: Vec<Box<dyn Player>> = thread_players.iter()
+                    .zip(receivers)
+                    .enumerate()
+                    .map(|(seat, (player, receiver))| match receiver {
+                        Some(decisions) => {
+                            let shared = Arc::clone(&thread_shared);
+                            Box::new(WebPlayer { seat, shared, decisions }) as Box<dyn Player>
+                        }
+                        None => create_bot(player).unwrap(),
+                    })
+                    .collect();
+
+                play(Game::new(players, num_teams), &thread_players, &thread_shared);
+            }));
+
+            // report a panic (e.g. in a bot) as the game's error, rather than leaving it waiting
+            if let Err(payload) = played {
+                let message = payload.downcast_ref::<&str>().map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| String::from("unknown error"));
+                thread_shared.publish(|state| {
+                    state.awaiting = None;
+                    state.error = Some(format!("the game stopped: {message}"));
+                });
+            }
+        });
+
+        let config = GameConfig {
+            num_players,
+            num_teams,
+            hand_size: Game::hand_size(num_players),
+            winning_sequences: Game::winning_sequences(num_teams),
+            max_dead_card_exchanges: 1,
+        };
+        let game = WebGame {
+            players,
+            config,
+            shared,
+            decisions: Mutex::new(decisions),
+            last_requested: Mutex::new(Instant::now()),
+        };
+        game.wait_for_decision();
+        game
+    }
+
+    // waits until the game needs a decision from a human, is over or has stopped
+    fn wait_for_decision(&self) {
+        let state = self.shared.state.lock().unwrap();
+        let _ = self.shared.changed
+            .wait_timeout_while(state, DECISION_TIMEOUT, |state| {
+                state.awaiting.is_none() && state.result.is_none() && state.error.is_none()
+            })
+            .unwrap();
+    }
+
+    fn team(&self, seat: usize) -> Team {
+        Game::player_team(self.config.num_teams, seat)
+    }
+
+    fn to_json(&self, id: usize) -> Value {
+        let state = self.shared.state.lock().unwrap();
+
+        let players: Vec<Value> = self.players.iter()
+            .enumerate()
+            .map(|(seat, player)| json!({ "seat": seat, "team": self.team(seat).to_string(), "player": player }))
+            .collect();
+
+        let mut game = public_state_json(&state.board, &state.deck);
+        game["id"] = json!(id);
+        game["players"] = json!(players);
+        game["cards"] = cards_json(&state.board);
+        game["turns"] = json!(state.turns);
+        game["awaiting"] = match state.awaiting {
+            Some((seat, kind)) => json!({
+                "seat": seat,
+                "decision": if kind == DecisionKind::Move { "move" } else { "dead_card" },
+            }),
+            None => Value::Null,
+        };
+        game["result"] = match state.result {
+            Some(result) => json!({ "winner": result.winner.to_string(), "turns": result.turns }),
+            None => Value::Null,
+        };
+        game["error"] = json!(state.error);
+        game
+    }
+
+    fn hand(&self, seat: usize) -> Result<Value, ApiError> {
+        self.human_seat(seat)?;
+        let state = self.shared.state.lock().unwrap();
+        let hand = &state.hands[seat];
+
+        let moves: Vec<Value> = legal_moves(&self.team(seat), hand, &state.board).iter()
+            .filter_map(|play| play.card_index(hand).map(|index| move_json(&hand[index], play)))
+            .collect();
+        let dead_cards: Vec<String> = hand.iter()
+            .filter(|card| state.board.is_dead(card))
+            .map(|card| card.to_string())
+            .collect();
+
+        Ok(json!({
+            "seat": seat,
+            "team": self.team(seat).to_string(),
+            "hand": hand.iter().map(|card| card.to_string()).collect::<Vec<String>>(),
+            "legal_moves": moves,
+            "dead_cards": dead_cards,
+        }))
+    }
+
+    fn submit_move(&self, body: &Value) -> Result<(), ApiError> {
+        let seat = parse_seat(body)?;
+        let (Some(card), Some(square)) = (body["card"].as_str(), body["square"].as_str()) else {
+            return Err((400, String::from("expected a card and a square")));
+        };
+
+        let card: Card = card.parse().map_err(|error| (400, error))?;
+        let play = Move::for_card(card, square.parse().map_err(|error| (400, error))?);
+        self.decide(seat, DecisionKind::Move, |state| {
+            play.validate(&self.team(seat), &state.hands[seat], &state.board).map_err(|error| (400, error))?;
+            Ok(Decision::Move(play))
+        })
+    }
+
+    fn submit_exchange(&self, body: &Value) -> Result<(), ApiError> {
+        let seat = parse_seat(body)?;
+        self.decide(seat, DecisionKind::DeadCard, |state| {
+            let index = match &body["card"] {
+                Value::Null => None,
+                card => {
+                    let card: Card = card.as_str().unwrap_or_default().parse().map_err(|error| (400, error))?;
+                    let index = state.hands[seat].iter()
+                        .position(|hand_card| *hand_card == card && state.board.is_dead(hand_card))
+                        .ok_or_else(|| (400, format!("{card} is not a dead card in the hand")))?;
+                    Some(index)
+                }
+            };
+            Ok(Decision::DeadCard(index))
+        })
+    }
+
+    fn suggest(&self, seat: usize, bot: &str) -> Result<Value, ApiError> {
+        self.human_seat(seat)?;
+        let mut player = create_bot(bot).ok_or_else(|| (400, format!("unknown bot: {bot}")))?;
+
+        let state = self.shared.state.lock().unwrap();
+        if state.awaiting != Some((seat, DecisionKind::Move)) {
+            return Err((409, format!("seat {seat} is not choosing a move")));
+        }
+
+        let team = self.team(seat);
+        let hand = &state.hands[seat];
+        player.on_game_start(seat, &team, &self.config);
+        let play = player.play(&team, hand, &state.board, &state.deck);
+
+        let index = play.validate(&team, hand, &state.board).map_err(|error| (500, error))?;
+        Ok(move_json(&hand[index], &play))
+    }
+
+    fn human_seat(&self, seat: usize) -> Result<(), ApiError> {
+        match self.players.get(seat) {
+            Some(player) if player == HUMAN => Ok(()),
+            Some(_) => Err((403, format!("seat {seat} is not played by a human"))),
+            None => Err((404, format!("no such seat: {seat}"))),
+        }
+    }
+
+    // if the game is waiting on the given decision from the given human seat, sends it the decision
+    // made from the current state by the given function and waits until it needs the next one;
+    // checking and sending under the same lock ensures only one of any concurrent requests for the
+    // same decision is sent
+    fn decide<F>(&self, seat: usize, kind: DecisionKind, decision: F) -> Result<(), ApiError>
+        where F: FnOnce(&GameState) -> Result<Decision, ApiError> {
+        self.human_seat(seat)?;
+        {
+            let mut state = self.shared.state.lock().unwrap();
+            match state.awaiting {
+                Some(awaiting) if awaiting == (seat, kind) => {}
+                _ if state.result.is_some() => return Err((409, String::from("the game is over"))),
+                _ if state.error.is_some() => return Err((409, state.error.clone().unwrap())),
+                _ => return Err((409, format!("the game is not waiting on this decision from seat {seat}"))),
+            }
+
+            let decision = decision(&state)?;
+            state.awaiting = None;
+            self.decisions.lock().unwrap()[seat].as_ref()
+                .and_then(|decisions| decisions.send(decision).ok())
+                .ok_or_else(|| (500, String::from("the game has stopped")))?;
+        }
+
+        self.wait_for_decision();
+        Ok(())
+    }
+}
+
+// plays the given game until it is over, publishing its state after each turn
+fn play(mut game: Game, players: &[String], shared: &Shared) {
+    loop {
+        let result = game.run_turn();
+        shared.publish(|state| {
+            state.board = game.board().clone();
+            state.deck = game.deck().public_copy();
+            for (seat, hand) in state.hands.iter_mut().enumerate() {
+                if players[seat] == HUMAN {
+                    *hand = game.hand(seat).to_vec();
+                }
+            }
+            state.turns = game.turn_count();
+            state.result = result;
+        });
+
+        if result.is_some() {
+            break;
+        }
+    }
+}
+
+impl Shared {
+    fn publish<F>(&self, update: F) where F: FnOnce(&mut GameState) {
+        update(&mut self.state.lock().unwrap());
+        self.changed.notify_all();
+    }
+}
+
+// player for a human seat, which waits for decisions submitted through the API
+struct WebPlayer {
+    seat: usize,
+    shared: Arc<Shared>,
+    decisions: Receiver<Decision>,
+}
+
+impl WebPlayer {
+    // publishes the state the decision is made in, then waits for it
+    fn decide(&self, kind: DecisionKind, hand: &[Card], board: &Board, deck: &Deck) -> Decision {
+        self.shared.publish(|state| {
+            state.board = board.clone();
+            state.deck = deck.public_copy();
+            state.hands[self.seat] = hand.to_vec();
+            state.awaiting = Some((self.seat, kind));
+        });
+
+        // the decision has been validated by the API; once the game is removed (or the server
+        // stops), unwind to end the game's thread without reporting a panic
+        self.decisions.recv().unwrap_or_else(|_| panic::resume_unwind(Box::new("the game was abandoned")))
+    }
+}
+
+impl Player for WebPlayer {
+    fn play(&mut self, _team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Move {
+        match self.decide(DecisionKind::Move, hand, board, deck) {
+            Decision::Move(play) => play,
+            Decision::DeadCard(_) => panic!("expected a move"),
+        }
+    }
+
+    fn replace_dead_card(&mut self, _team: &Team, hand: &[Card], board: &Board, deck: &Deck) -> Option<usize> {
+        match self.decide(DecisionKind::DeadCard, hand, board, deck) {
+            Decision::DeadCard(index) => index,
+            Decision::Move(_) => panic!("expected a dead card decision"),
+        }
+    }
+}
+
+fn parse_body(body: &str) -> Result<Value, ApiError> {
+    serde_json::from_str(body).map_err(|error| (400, format!("invalid JSON: {error}")))
+}
+
+fn parse_seat(body: &Value) -> Result<usize, ApiError> {
+    body["seat"].as_u64().map(|seat| seat as usize).ok_or_else(|| (400, String::from("expected a seat")))
+}
+
+fn parse_number(token: &str) -> Result<usize, ApiError> {
+    token.parse().map_err(|_| (400, format!("invalid number: {token}")))
+}
+
+fn respond(request: Request, status: u16, content_type: &str, body: String) -> io::Result<()> {
+    let header = Header::from_bytes("Content-Type", content_type).unwrap();
+    let length = body.len();
+    let response = Response::new(status.into(), vec![header], Cursor::new(body.into_bytes()), Some(length), None);
+    request.respond(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    use super::*;
+
+    fn start_server() -> SocketAddr {
+        start_server_with_expiry(GAME_EXPIRY)
+    }
+
+    fn start_server_with_expiry(game_expiry: Duration) -> SocketAddr {
+        let mut server = WebServer::bind("127.0.0.1:0").unwrap();
+        server.game_expiry = game_expiry;
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        address
+    }
+
+    // makes a request to the server, returning the status and the JSON body of the response
+    fn request(address: SocketAddr, method: &str, path: &str, body: Option<Value>) -> (u16, Value) {
+        let body = body.map_or(String::new(), |body| body.to_string());
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len(),
+        ).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn plays_a_game_against_a_bot_through_the_api() {
+        let address = start_server();
+
+        let (status, mut game) = request(address, "POST", "/games", Some(json!({ "players": ["human", "random"] })));
+        assert_eq!(status, 200);
+        let id = game["id"].as_u64().unwrap();
+        assert_eq!(game["players"][1]["player"], "random");
+
+        // a bot's hand is private
+        assert_eq!(request(address, "GET", &format!("/games/{id}/hands/1"), None).0, 403);
+
+        while game["result"].is_null() {
+            assert_eq!(game["awaiting"]["seat"], 0);
+            let (_, hand) = request(address, "GET", &format!("/games/{id}/hands/0"), None);
+            assert_eq!(hand["team"], "BLUE");
+
+            if game["awaiting"]["decision"] == "dead_card" {
+                let body = json!({ "seat": 0, "card": hand["dead_cards"][0] });
+                game = request(address, "POST", &format!("/games/{id}/exchanges"), Some(body)).1;
+                continue;
+            }
+
+            // a move which is not in the hand is rejected, without affecting the game
+            let body = json!({ "seat": 0, "card": "Jx", "square": "a1" });
+            assert_eq!(request(address, "POST", &format!("/games/{id}/moves"), Some(body)).0, 400);
+
+            let (status, suggestion) = request(address, "GET", &format!("/games/{id}/suggestions/0"), None);
+            assert_eq!(status, 200);
+            assert!(hand["legal_moves"].as_array().unwrap().contains(&suggestion));
+
+            let body = json!({ "seat": 0, "card": suggestion["card"], "square": suggestion["square"] });
+            let (status, next) = request(address, "POST", &format!("/games/{id}/moves"), Some(body));
+            assert_eq!(status, 200);
+            game = next;
+        }
+
+        let (status, finished) = request(address, "GET", &format!("/games/{id}"), None);
+        assert_eq!(status, 200);
+        assert_eq!(finished["result"], game["result"]);
+        let body = json!({ "seat": 0, "card": "2♠", "square": "b0" });
+        assert_eq!(request(address, "POST", &format!("/games/{id}/moves"), Some(body)).0, 409);
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let address = start_server();
+
+        assert_eq!(request(address, "GET", "/games/0", None).0, 404);
+        assert_eq!(request(address, "GET", "/nowhere", None).0, 404);
+        assert_eq!(request(address, "POST", "/games", Some(json!({ "players": ["human", "nobody"] }))).0, 400);
+        assert_eq!(request(address, "POST", "/games", Some(json!({ "players": ["human"] }))).0, 400);
+
+        // a game between bots plays out straight away
+        let (status, game) = request(address, "POST", "/games", Some(json!({ "players": ["random", "random"] })));
+        assert_eq!(status, 200);
+        assert!(!game["result"].is_null());
+    }
+
+    #[test]
+    fn removes_expired_games() {
+        let address = start_server_with_expiry(Duration::from_millis(200));
+
+        let (_, game) = request(address, "POST", "/games", Some(json!({ "players": ["random", "random"] })));
+        let id = game["id"].as_u64().unwrap();
+        assert_eq!(request(address, "GET", &format!("/games/{id}"), None).0, 200);
+
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(request(address, "GET", &format!("/games/{id}"), None).0, 404);
+
+        // ids of removed games are not reused
+        let (_, game) = request(address, "POST", "/games", Some(json!({ "players": ["random", "random"] })));
+        assert_eq!(game["id"], id + 1);
+    }
+
+    // panics on its first move
+    struct PanickingPlayer {}
+
+    impl Player for PanickingPlayer {
+        fn play(&mut self, _team: &Team, _hand: &[Card], _board: &Board, _deck: &Deck) -> Move {
+            panic!("no move")
+        }
+    }
+
+    #[test]
+    fn reports_a_bot_panic_as_the_game_error() {
+        let players = vec![String::from("random"), String::from(HUMAN)];
+        let game = WebGame::start(players, 2, |_| Some(Box::new(PanickingPlayer {})));
+
+        let json = game.to_json(0);
+        assert_eq!(json["error"], "the game stopped: no move");
+        assert!(json["awaiting"].is_null());
+        assert!(json["result"].is_null());
+
+        let body = json!({ "seat": 1, "card": "2♠", "square": "b0" });
+        assert_eq!(game.submit_move(&body).unwrap_err(), (409, String::from("the game stopped: no move")));
+    }
+}